@@ -1,6 +1,8 @@
 extern crate criterion;
 extern crate num_traits;
 extern crate ordsearch;
+#[cfg(feature = "rayon")]
+extern crate rayon;
 
 use criterion::{
     criterion_group, criterion_main, measurement::WallTime, AxisScale, BatchSize, BenchmarkGroup,
@@ -29,11 +31,23 @@ criterion_group!(
     benchmarks_for::<u32, { u32::MAX as usize }>,
     benchmarks_for::<u64, { u64::MAX as usize }>,
     benchmarks_for::<u128, { u64::MAX as usize }>,
+    benchmarks_for::<usize, { usize::MAX }>,
+    benchmarks_for::<Large, { u64::MAX as usize }>,
 );
 
+/// Element type that occupies a whole cache line
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+struct Large([u64; 8]);
+
+impl From<usize> for Large {
+    fn from(value: usize) -> Self {
+        Large([value as u64; 8])
+    }
+}
+
 fn benchmarks_for<T, const MAX: usize>(c: &mut Criterion)
 where
-    T: TryFrom<usize> + Ord + Copy,
+    T: TryFrom<usize> + Ord + Copy + Send,
 {
     let plot_config = PlotConfiguration::default().summary_scale(AxisScale::Logarithmic);
 
@@ -59,6 +73,14 @@ where
                 i,
                 false,
             );
+            search_bench_case::<MAX, T, _>(
+                "sorted_vec_partition_point",
+                make_sorted_vec,
+                search_sorted_vec_partition_point,
+                &mut group,
+                i,
+                false,
+            );
             search_bench_case::<MAX, T, _>(
                 "btreeset",
                 make_btreeset,
@@ -75,6 +97,14 @@ where
                 i,
                 false,
             );
+            search_bench_case::<MAX, T, _>(
+                "ordsearch_branchy",
+                make_this,
+                search_this_branchy,
+                &mut group,
+                i,
+                false,
+            );
         }
         group.finish();
     }
@@ -112,6 +142,194 @@ where
                 i,
                 true,
             );
+            search_bench_case::<MAX, T, _>(
+                "ordsearch_branchy",
+                make_this,
+                search_this_branchy,
+                &mut group,
+                i,
+                true,
+            );
+        }
+        group.finish();
+    }
+
+    {
+        // prefetching is only enabled on x86 or with the `nightly` feature, otherwise both cases are the same
+        let groupname = format!("Search (prefetch) {}", type_name::<T>());
+        let mut group = c.benchmark_group(groupname);
+        group
+            .warm_up_time(WARM_UP_TIME)
+            .measurement_time(MEASUREMENT_TIME)
+            .plot_config(plot_config.clone());
+
+        for i in sizes {
+            search_bench_case::<MAX, T, _>(
+                "ordsearch",
+                make_this,
+                search_this,
+                &mut group,
+                i,
+                false,
+            );
+            search_bench_case::<MAX, T, _>(
+                "ordsearch_no_prefetch",
+                make_this,
+                search_this_without_prefetch,
+                &mut group,
+                i,
+                false,
+            );
+            search_bench_case::<MAX, T, _>(
+                "ordsearch_prefetch_2_levels",
+                make_this,
+                search_this_with_prefetch_levels::<2, T>,
+                &mut group,
+                i,
+                false,
+            );
+            search_bench_case::<MAX, T, _>(
+                "ordsearch_prefetch_4_levels",
+                make_this,
+                search_this_with_prefetch_levels::<4, T>,
+                &mut group,
+                i,
+                false,
+            );
+            search_bench_case::<MAX, T, _>(
+                "ordsearch_prefetch_6_levels",
+                make_this,
+                search_this_with_prefetch_levels::<6, T>,
+                &mut group,
+                i,
+                false,
+            );
+        }
+        group.finish();
+    }
+
+    {
+        // the linear tail is aimed at collections that fit in L2/L3, so only those sizes are measured
+        let groupname = format!("Search (linear tail) {}", type_name::<T>());
+        let mut group = c.benchmark_group(groupname);
+        group
+            .warm_up_time(WARM_UP_TIME)
+            .measurement_time(MEASUREMENT_TIME)
+            .plot_config(plot_config.clone());
+
+        for i in [16384, 65536, 262144, 1048576] {
+            search_bench_case::<MAX, T, _>(
+                "ordsearch",
+                make_this,
+                search_this,
+                &mut group,
+                i,
+                false,
+            );
+            search_bench_case::<MAX, T, _>(
+                "ordsearch_linear_tail_2_levels",
+                make_this,
+                search_this_linear_tail::<2, T>,
+                &mut group,
+                i,
+                false,
+            );
+            search_bench_case::<MAX, T, _>(
+                "ordsearch_linear_tail_3_levels",
+                make_this,
+                search_this_linear_tail::<3, T>,
+                &mut group,
+                i,
+                false,
+            );
+            search_bench_case::<MAX, T, _>(
+                "ordsearch_linear_tail_4_levels",
+                make_this,
+                search_this_linear_tail::<4, T>,
+                &mut group,
+                i,
+                false,
+            );
+        }
+        group.finish();
+    }
+
+    {
+        let groupname = format!("Search (fanout) {}", type_name::<T>());
+        let mut group = c.benchmark_group(groupname);
+        group
+            .warm_up_time(WARM_UP_TIME)
+            .measurement_time(MEASUREMENT_TIME)
+            .plot_config(plot_config.clone());
+
+        for i in [1024, 65536, 1048576, 10485760] {
+            search_bench_case::<MAX, T, _>(
+                "ordsearch",
+                make_this,
+                search_this,
+                &mut group,
+                i,
+                false,
+            );
+            search_bench_case::<MAX, T, _>(
+                "ordsearch_fanout_2",
+                make_this_fanout::<2, T>,
+                search_this_fanout::<2, T>,
+                &mut group,
+                i,
+                false,
+            );
+            search_bench_case::<MAX, T, _>(
+                "ordsearch_fanout_4",
+                make_this_fanout::<4, T>,
+                search_this_fanout::<4, T>,
+                &mut group,
+                i,
+                false,
+            );
+            search_bench_case::<MAX, T, _>(
+                "ordsearch_fanout_8",
+                make_this_fanout::<8, T>,
+                search_this_fanout::<8, T>,
+                &mut group,
+                i,
+                false,
+            );
+            search_bench_case::<MAX, T, _>(
+                "ordsearch_fanout_16",
+                make_this_fanout::<16, T>,
+                search_this_fanout::<16, T>,
+                &mut group,
+                i,
+                false,
+            );
+        }
+        group.finish();
+    }
+
+    {
+        let groupname = format!("Search (out of range) {}", type_name::<T>());
+        let mut group = c.benchmark_group(groupname);
+        group
+            .warm_up_time(WARM_UP_TIME)
+            .measurement_time(MEASUREMENT_TIME)
+            .plot_config(plot_config.clone());
+
+        for i in sizes {
+            out_of_range_bench_case::<MAX, T, _>(
+                "sorted_vec",
+                make_sorted_vec,
+                search_sorted_vec,
+                &mut group,
+                i,
+            );
+            out_of_range_bench_case::<MAX, T, _>(
+                "ordsearch",
+                make_this,
+                search_this,
+                &mut group,
+                i,
+            );
         }
         group.finish();
     }
@@ -134,6 +352,64 @@ where
             );
             construction_bench_case::<MAX, T, _>("btreeset", make_btreeset, &mut group, i, false);
             construction_bench_case::<MAX, T, _>("ordsearch", make_this, &mut group, i, false);
+            construction_bench_case::<MAX, T, _>(
+                "ordsearch_from_sorted_vec",
+                make_this_from_sorted_vec,
+                &mut group,
+                i,
+                false,
+            );
+        }
+        group.finish();
+    }
+
+    {
+        // all levels of perfect trees (n = 2^k - 1) are full, compare them with the next size
+        let groupname = format!("Construction (perfect tree) {}", type_name::<T>());
+        let mut group = c.benchmark_group(groupname);
+        group
+            .warm_up_time(WARM_UP_TIME)
+            .measurement_time(MEASUREMENT_TIME)
+            .plot_config(plot_config.clone());
+
+        for k in [4, 8, 12, 16, 20] {
+            let perfect = (1 << k) - 1;
+            construction_bench_case::<MAX, T, _>(
+                "ordsearch",
+                make_this,
+                &mut group,
+                perfect,
+                false,
+            );
+            construction_bench_case::<MAX, T, _>(
+                "ordsearch",
+                make_this,
+                &mut group,
+                perfect + 1,
+                false,
+            );
+        }
+        group.finish();
+    }
+
+    #[cfg(feature = "rayon")]
+    {
+        let groupname = format!("Construction (parallel) {}", type_name::<T>());
+        let mut group = c.benchmark_group(groupname);
+        group
+            .warm_up_time(WARM_UP_TIME)
+            .measurement_time(MEASUREMENT_TIME)
+            .plot_config(plot_config.clone());
+
+        for i in [65536, 1048576, 10485760] {
+            construction_bench_case::<MAX, T, _>("ordsearch", make_this, &mut group, i, false);
+            construction_bench_case::<MAX, T, _>(
+                "ordsearch_parallel",
+                make_this_parallel,
+                &mut group,
+                i,
+                false,
+            );
         }
         group.finish();
     }
@@ -194,6 +470,31 @@ fn search_bench_case<const MAX: usize, T, Coll>(
     });
 }
 
+fn out_of_range_bench_case<const MAX: usize, T, Coll>(
+    name: &str,
+    setup_fun: impl Fn(Vec<T>) -> Coll,
+    search_fun: impl Fn(&Coll, T) -> Option<&T>,
+    group: &mut BenchmarkGroup<WallTime>,
+    size: usize,
+) where
+    T: TryFrom<usize> + Ord + Copy,
+{
+    group.bench_with_input(BenchmarkId::new(name, size), &size, |b, &size| {
+        // payload only contains values from the lower half of the domain: [0, MAX / 2)
+        let v: Vec<T> = (0usize..)
+            .map(|i| i % (MAX / 2))
+            .map(|i| T::try_from(i).ok().unwrap())
+            .take(size)
+            .collect();
+
+        // all queries are taken from the upper half of the domain: [MAX / 2, MAX)
+        let mut r =
+            pseudorandom_iter::<usize>(MAX / 2).map(|i| T::try_from(i + MAX / 2).ok().unwrap());
+        let c = setup_fun(v);
+        b.iter(|| search_fun(&c, r.next().unwrap()))
+    });
+}
+
 fn construction_bench_case<const MAX: usize, T, Coll>(
     name: &str,
     setup_fun: impl Fn(Vec<T>) -> Coll,
@@ -222,10 +523,52 @@ fn make_this<T: Ord>(mut v: Vec<T>) -> OrderedCollection<T> {
     OrderedCollection::from_sorted_iter(v)
 }
 
+fn make_this_from_sorted_vec<T: Ord>(mut v: Vec<T>) -> OrderedCollection<T> {
+    v.sort_unstable();
+    OrderedCollection::from_sorted_vec(v)
+}
+
+#[cfg(feature = "rayon")]
+fn make_this_parallel<T: Ord + Send>(v: Vec<T>) -> OrderedCollection<T> {
+    use rayon::iter::{IntoParallelIterator, ParallelIterator};
+    v.into_par_iter().collect()
+}
+
 fn search_this<T: Ord>(c: &OrderedCollection<T>, x: T) -> Option<&T> {
     c.find_gte(x)
 }
 
+fn search_this_branchy<T: Ord>(c: &OrderedCollection<T>, x: T) -> Option<&T> {
+    c.find_gte_branchy(x)
+}
+
+fn search_this_without_prefetch<T: Ord>(c: &OrderedCollection<T>, x: T) -> Option<&T> {
+    c.find_gte_without_prefetch(x)
+}
+
+fn search_this_with_prefetch_levels<const LEVELS: u32, T: Ord>(
+    c: &OrderedCollection<T>,
+    x: T,
+) -> Option<&T> {
+    c.find_gte_with_prefetch_levels::<LEVELS, _>(x)
+}
+
+fn search_this_linear_tail<const LEVELS: u32, T: Ord>(
+    c: &OrderedCollection<T>,
+    x: T,
+) -> Option<&T> {
+    c.find_gte_linear_tail::<LEVELS, _>(x)
+}
+
+fn make_this_fanout<const B: usize, T: Ord>(mut v: Vec<T>) -> OrderedCollection<T, B> {
+    v.sort_unstable();
+    OrderedCollection::from_sorted_iter_fanout(v)
+}
+
+fn search_this_fanout<const B: usize, T: Ord>(c: &OrderedCollection<T, B>, x: T) -> Option<&T> {
+    c.find_gte(x)
+}
+
 fn make_btreeset<T: Ord>(v: Vec<T>) -> BTreeSet<T> {
     use std::iter::FromIterator;
     BTreeSet::from_iter(v)
@@ -246,6 +589,12 @@ fn search_sorted_vec<T: Ord>(c: &Vec<T>, x: T) -> Option<&T> {
     c.binary_search(&x).ok().map(|i| &c[i])
 }
 
+/// Same lookup as `find_gte()`, unlike `binary_search()`, which only finds exact matches.
+#[allow(clippy::ptr_arg)]
+fn search_sorted_vec_partition_point<T: Ord>(c: &Vec<T>, x: T) -> Option<&T> {
+    c.get(c.partition_point(|v| *v < x))
+}
+
 fn pseudorandom_iter<T>(max: usize) -> impl Iterator<Item = T>
 where
     T: TryFrom<usize>,
@@ -0,0 +1,169 @@
+//! Zero-copy archival with [rkyv](https://docs.rs/rkyv).
+//!
+//! The archived form of an [`OrderedCollection`] is its Eytzinger layout as returned by
+//! [`OrderedCollection::as_eytzinger_slice()`], stored in an [`ArchivedVec`]. So an archive can be
+//! searched in place, e.g. straight from a memory-mapped file, without deserializing or reordering
+//! the elements first.
+//!
+//! rkyv 0.8 needs Rust 1.81, so the `rkyv` feature does too, unlike the rest of the crate.
+
+use crate::{search_eytzinger, OrderedCollection};
+use alloc::vec::Vec;
+use core::borrow::Borrow;
+use rkyv::{
+    bytecheck::CheckBytes,
+    rancor::{Fallible, Source},
+    ser::{Allocator, Writer},
+    vec::{ArchivedVec, VecResolver},
+    Archive, Deserialize, DeserializeUnsized, Place, Portable, Serialize,
+};
+
+/// The archived form of an [`OrderedCollection`].
+///
+/// It holds the archived elements in the same Eytzinger layout as the collection it was created
+/// from, and supports the same lookups without deserializing them.
+///
+/// Validating an archive (with the `bytecheck` feature of rkyv) only ensures that it is safe to
+/// access, not that the elements are in Eytzinger layout. Lookups in an archive that was not
+/// created from an `OrderedCollection` give unspecified results, but are safe.
+///
+/// # Examples
+///
+/// ```
+/// # extern crate rkyv;
+/// # use ordsearch::{ArchivedOrderedCollection, OrderedCollection};
+/// use rkyv::{rancor::Error, string::ArchivedString};
+///
+/// let x = OrderedCollection::from(vec!["apple".to_string(), "hello".to_string()]);
+/// let bytes = rkyv::to_bytes::<Error>(&x).unwrap();
+///
+/// let archived = rkyv::access::<ArchivedOrderedCollection<ArchivedString>, Error>(&bytes).unwrap();
+/// assert_eq!(archived.find_gte_ref("banana").map(|s| s.as_str()), Some("hello"));
+/// assert_eq!(archived.find_gte_ref("zebra"), None);
+///
+/// let y: OrderedCollection<String> = rkyv::deserialize::<_, Error>(archived).unwrap();
+/// assert_eq!(x, y);
+/// ```
+#[repr(transparent)]
+pub struct ArchivedOrderedCollection<T> {
+    items: ArchivedVec<T>,
+}
+
+// SAFETY: `ArchivedOrderedCollection` is a transparent wrapper around the portable `ArchivedVec`
+unsafe impl<T: Portable> Portable for ArchivedOrderedCollection<T> {}
+
+// SAFETY: `ArchivedOrderedCollection` is a transparent wrapper around `ArchivedVec`, so it is valid
+// exactly when the `ArchivedVec` is
+unsafe impl<T, C> CheckBytes<C> for ArchivedOrderedCollection<T>
+where
+    ArchivedVec<T>: CheckBytes<C>,
+    C: Fallible + ?Sized,
+{
+    unsafe fn check_bytes(value: *const Self, context: &mut C) -> Result<(), C::Error> {
+        ArchivedVec::<T>::check_bytes(value.cast(), context)
+    }
+}
+
+impl<T> ArchivedOrderedCollection<T> {
+    /// Find the smallest value `v` such that `v >= *x`, taking the query by reference.
+    ///
+    /// Returns `None` if there is no such `v`. Returns the same element as
+    /// [`OrderedCollection::find_gte_ref()`] does on the collection that was archived.
+    pub fn find_gte_ref<X>(&self, x: &X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord + ?Sized,
+    {
+        let items = self.as_eytzinger_slice();
+        search_eytzinger(items, x).map(|k| &items[k])
+    }
+
+    /// Returns the archived elements in the order they are stored.
+    ///
+    /// See [`OrderedCollection::as_eytzinger_slice()`].
+    pub fn as_eytzinger_slice(&self) -> &[T] {
+        self.items.as_slice()
+    }
+
+    /// Returns the number of elements in the archived collection.
+    pub fn len(&self) -> usize {
+        self.items.len()
+    }
+
+    /// Returns `true` if the archived collection contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.items.is_empty()
+    }
+}
+
+impl<T: Archive> Archive for OrderedCollection<T> {
+    type Archived = ArchivedOrderedCollection<T::Archived>;
+    type Resolver = VecResolver;
+
+    fn resolve(&self, resolver: Self::Resolver, out: Place<Self::Archived>) {
+        // SAFETY: `ArchivedOrderedCollection` is a transparent wrapper around `ArchivedVec`
+        let out = unsafe { out.cast_unchecked::<ArchivedVec<T::Archived>>() };
+        ArchivedVec::resolve_from_slice(self.as_eytzinger_slice(), resolver, out);
+    }
+}
+
+impl<T, S> Serialize<S> for OrderedCollection<T>
+where
+    T: Serialize<S>,
+    S: Fallible + Allocator + Writer + ?Sized,
+{
+    fn serialize(&self, serializer: &mut S) -> Result<Self::Resolver, S::Error> {
+        ArchivedVec::<T::Archived>::serialize_from_slice(self.as_eytzinger_slice(), serializer)
+    }
+}
+
+impl<T, D> Deserialize<OrderedCollection<T>, D> for ArchivedOrderedCollection<T::Archived>
+where
+    T: Archive,
+    [T::Archived]: DeserializeUnsized<[T], D>,
+    D: Fallible + ?Sized,
+    D::Error: Source,
+{
+    fn deserialize(&self, deserializer: &mut D) -> Result<OrderedCollection<T>, D::Error> {
+        let items: Vec<T> = self.items.deserialize(deserializer)?;
+        // the archive is already in Eytzinger layout
+        Ok(OrderedCollection::from_eytzinger_vec(items))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+    use rkyv::{rancor::Error, rend::u32_le};
+
+    #[test]
+    fn archived_search_matches() {
+        for n in 0..100 {
+            let x = OrderedCollection::from_sorted_iter((0..n).map(|v| 2 * v));
+            let bytes = rkyv::to_bytes::<Error>(&x).unwrap();
+            let archived =
+                rkyv::access::<ArchivedOrderedCollection<u32_le>, Error>(&bytes).unwrap();
+            assert_eq!(archived.len(), x.len());
+            for q in 0..2 * n + 2 {
+                assert_eq!(
+                    archived
+                        .find_gte_ref(&u32_le::from_native(q))
+                        .map(|v| v.to_native()),
+                    x.find_gte(q).copied()
+                );
+            }
+
+            let y: OrderedCollection<u32> = rkyv::deserialize::<_, Error>(archived).unwrap();
+            assert_eq!(x, y);
+        }
+    }
+
+    #[test]
+    fn rejects_truncated() {
+        let x = OrderedCollection::from(vec![1u32, 2, 3]);
+        let bytes = rkyv::to_bytes::<Error>(&x).unwrap();
+        let truncated = &bytes[..bytes.len() - 4];
+        assert!(rkyv::access::<ArchivedOrderedCollection<u32_le>, Error>(truncated).is_err());
+    }
+}
@@ -0,0 +1,222 @@
+//! Fixed-capacity collection stored inline.
+
+use crate::{eytzinger_descend, last_right_turn, layout_rank, search_eytzinger, Prefetch};
+use core::{
+    borrow::Borrow,
+    mem::{ManuallyDrop, MaybeUninit},
+    ptr,
+};
+
+/// A fixed-size collection of ordered items that is stored inline, without any heap allocation.
+///
+/// This is the inline counterpart of [`OrderedCollection`](crate::OrderedCollection): the `N`
+/// elements are kept in the same Eytzinger layout and searched the same way, but the layout lives
+/// in an array of exactly `N` elements (there is no `[0]`), so it can be put on the stack or in a
+/// `static`. It doesn't need the `alloc` feature.
+///
+/// # Examples
+///
+/// ```
+/// # use ordsearch::OrderedArray;
+/// let x = OrderedArray::from_sorted_array([1, 2, 4, 8, 16, 32, 64]);
+/// assert_eq!(x.find_gte(0), Some(&1));
+/// assert_eq!(x.find_gte(3), Some(&4));
+/// assert_eq!(x.find_gte(64), Some(&64));
+/// assert_eq!(x.find_gte(65), None);
+/// ```
+pub struct OrderedArray<T, const N: usize> {
+    /// `nodes[k]` holds node `k + 1` of the Eytzinger layout
+    nodes: [T; N],
+}
+
+impl<T, const N: usize> OrderedArray<T, N> {
+    /// Construct a new `OrderedArray` from an array of sorted elements.
+    ///
+    /// Note that if the array is *not* sorted, no error will be given, but lookups will give
+    /// incorrect results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedArray;
+    /// let x = OrderedArray::from_sorted_array([7, 12, 42, 89]);
+    /// assert_eq!(x.find_gte(50), Some(&89));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `N >= isize::MAX` (see
+    /// [`OrderedCollection::from_sorted_iter()`](crate::OrderedCollection::from_sorted_iter)).
+    pub fn from_sorted_array(sorted: [T; N]) -> Self {
+        assert!(
+            N < isize::MAX as usize,
+            "OrderedArray must hold fewer than isize::MAX elements"
+        );
+        // the elements are moved out one by one below
+        let sorted = ManuallyDrop::new(sorted);
+        // SAFETY: an array of `MaybeUninit` doesn't need to be initialized
+        let mut nodes: [MaybeUninit<T>; N] = unsafe { MaybeUninit::uninit().assume_init() };
+        for (k, node) in nodes.iter_mut().enumerate() {
+            // SAFETY: layout_rank() maps 1..=N to 0..N one-to-one, so every element is moved exactly once
+            *node = MaybeUninit::new(unsafe { ptr::read(&sorted[layout_rank(N, k + 1)]) });
+        }
+        // SAFETY: all the nodes are initialized, and `MaybeUninit<T>` has the same layout as `T`
+        let nodes = unsafe { ptr::read(nodes.as_ptr().cast::<[T; N]>()) };
+        OrderedArray { nodes }
+    }
+
+    /// Construct a new `OrderedArray` from an array of sorted elements in a `const` context.
+    ///
+    /// This is the same as [`from_sorted_array()`](Self::from_sorted_array), but requires `T` to be
+    /// [`Copy`], so that the layout can be built at compile time, e.g. for a `static` or a `const`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedArray;
+    /// static PRIMES: OrderedArray<u32, 6> = OrderedArray::from_sorted_array_const([2, 3, 5, 7, 11, 13]);
+    /// assert_eq!(PRIMES.find_gte(8), Some(&11));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics (or fails to compile in a `const` context) if `N >= isize::MAX`.
+    pub const fn from_sorted_array_const(sorted: [T; N]) -> Self
+    where
+        T: Copy,
+    {
+        assert!(
+            N < isize::MAX as usize,
+            "OrderedArray must hold fewer than isize::MAX elements"
+        );
+        let mut nodes = sorted;
+        let mut k = 0;
+        // iterators are not available in const fn
+        while k < N {
+            nodes[k] = sorted[layout_rank(N, k + 1)];
+            k += 1;
+        }
+        OrderedArray { nodes }
+    }
+
+    /// Find the smallest value `v` such that `v >= x`.
+    ///
+    /// Returns `None` if there is no such `v`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedArray;
+    /// let x = OrderedArray::from_sorted_array([1, 2, 4, 8]);
+    /// assert_eq!(x.find_gte(3), Some(&4));
+    /// assert_eq!(x.find_gte(9), None);
+    /// ```
+    pub fn find_gte<X>(&self, x: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        search_eytzinger(&self.nodes, x.borrow()).map(|k| &self.nodes[k])
+    }
+
+    /// Find the largest value `v` such that `v <= x`.
+    ///
+    /// Returns `None` if there is no such `v`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedArray;
+    /// let x = OrderedArray::from_sorted_array([1, 2, 4, 8]);
+    /// assert_eq!(x.find_lte(3), Some(&2));
+    /// assert_eq!(x.find_lte(0), None);
+    /// ```
+    pub fn find_lte<X>(&self, x: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        self.node(last_right_turn(self.descend(|value| x >= value.borrow())))
+    }
+
+    /// Returns the number of elements in the collection.
+    pub fn len(&self) -> usize {
+        N
+    }
+
+    /// Returns `true` if the collection contains no elements.
+    pub fn is_empty(&self) -> bool {
+        N == 0
+    }
+
+    /// Returns the elements in the order they are stored internally.
+    ///
+    /// See [`OrderedCollection::as_eytzinger_slice()`](crate::OrderedCollection::as_eytzinger_slice).
+    pub fn as_eytzinger_slice(&self) -> &[T] {
+        &self.nodes
+    }
+
+    /// See `OrderedCollection::descend()`.
+    #[inline(always)]
+    fn descend<F>(&self, go_right: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        eytzinger_descend(&self.nodes, Prefetch::<T>::prefetch, go_right)
+    }
+
+    /// Returns the value of node `i` of the layout, or `None` if the index is 0.
+    #[inline(always)]
+    fn node(&self, i: usize) -> Option<&T> {
+        i.checked_sub(1).map(|k| &self.nodes[k])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::array;
+
+    #[test]
+    #[cfg(feature = "alloc")]
+    fn matches_collection() {
+        use crate::OrderedCollection;
+
+        fn check<const N: usize>() {
+            let x = OrderedArray::from_sorted_array(array::from_fn::<u32, N, _>(|v| 2 * v as u32));
+            let y = OrderedCollection::from_sorted_iter((0..N as u32).map(|v| 2 * v));
+            assert_eq!(x.len(), N);
+            assert_eq!(x.as_eytzinger_slice(), y.as_eytzinger_slice());
+            for q in 0..2 * N as u32 + 2 {
+                assert_eq!(x.find_gte(q), y.find_gte(q));
+                assert_eq!(x.find_lte(q), y.find_lte(q));
+            }
+        }
+        check::<0>();
+        check::<1>();
+        check::<2>();
+        check::<7>();
+        check::<8>();
+        check::<100>();
+    }
+
+    #[test]
+    fn const_matches_runtime() {
+        const X: OrderedArray<u32, 7> =
+            OrderedArray::from_sorted_array_const([1, 2, 3, 4, 5, 6, 7]);
+        assert_eq!(X.as_eytzinger_slice(), [4, 2, 6, 1, 3, 5, 7]);
+
+        fn check<const N: usize>() {
+            let sorted = array::from_fn::<u32, N, _>(|v| 2 * v as u32);
+            let x = OrderedArray::from_sorted_array_const(sorted);
+            let y = OrderedArray::from_sorted_array(sorted);
+            assert_eq!(x.as_eytzinger_slice(), y.as_eytzinger_slice());
+        }
+        check::<0>();
+        check::<1>();
+        check::<2>();
+        check::<8>();
+        check::<100>();
+    }
+}
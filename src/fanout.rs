@@ -0,0 +1,243 @@
+//! Experimental layout with several keys per node (see [`OrderedCollection`]).
+//!
+//! Node `k` holds the keys `[k * B, (k + 1) * B)` of the layout, and its children are the nodes
+//! `k * (B + 1) + 1 ..= k * (B + 1) + B + 1`, the same as in a B-tree that is stored level by level.
+//! With `B = 1`, this is exactly the Eytzinger layout (only 0-based), so the layouts match, and the
+//! keys are stored after the unused `[0]` of the binary layout.
+//!
+//! Unlike the binary layout, nodes are not filled from ranks directly, but by walking the tree in
+//! order. Indices are computed with saturating arithmetic, which can only overflow for nodes that
+//! are well past the end of the layout anyway.
+//!
+//! With `B > 1`, the layout is stored 0-based, without an unused slot, so the first node starts at
+//! the cache line boundary the storage is aligned to. A node of a cache line in size (like 16
+//! `u32`s) then takes exactly one cache line.
+
+use crate::OrderedCollection;
+use core::{borrow::Borrow, mem::MaybeUninit};
+
+impl<T, const B: usize> OrderedCollection<T, B> {
+    /// Construct a new `OrderedCollection` with `B` keys per node from an iterator over sorted
+    /// elements.
+    ///
+    /// Note that if the iterator is *not* sorted, no error will be given, but lookups will give
+    /// incorrect results. With `B = 1`, the layout is the same as the one built by
+    /// [`from_sorted_iter()`](OrderedCollection::from_sorted_iter).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `B` is 0, or if the iterator yields `isize::MAX` elements or more.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::<_, 4>::from_sorted_iter_fanout(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.as_eytzinger_slice(), [8, 16, 32, 64, 1, 2, 4]);
+    /// assert_eq!(x.find_gte(3), Some(&4));
+    /// assert_eq!(x.find_gte(65), None);
+    /// ```
+    pub fn from_sorted_iter_fanout<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+    {
+        assert!(B > 0, "OrderedCollection needs at least one key per node");
+        let mut iter = iter.into_iter();
+        let n = iter.len();
+        let mut storage = Self::allocate(n);
+        fill::<_, _, B>(&mut storage[Self::FIRST..], 0, &mut iter);
+
+        // the smallest key is the first one of the leftmost node, and the largest one is the last
+        // key of the rightmost node
+        let (min, max) = if n > 0 {
+            let mut k = 0;
+            while first_key::<B>(child::<B>(k, 0)) < n {
+                k = child::<B>(k, 0);
+            }
+            let min = first_key::<B>(k);
+
+            let mut k = 0;
+            while first_key::<B>(child::<B>(k, keys::<B>(k, n))) < n {
+                k = child::<B>(k, keys::<B>(k, n));
+            }
+            let max = first_key::<B>(k) + keys::<B>(k, n) - 1;
+
+            // `min` and `max` are 1-based for any fanout (see `OrderedCollection::node()`)
+            (min + 1, max + 1)
+        } else {
+            (0, 0)
+        };
+
+        OrderedCollection { storage, min, max }
+    }
+}
+
+/// Find the position of the smallest value `v` such that `v >= x` in a slice with `B` keys per node.
+///
+/// The keys of a node are compared all at once rather than one by one, which the compiler can
+/// vectorize for primitive types. This also keeps the loop free of branches, apart from the one on
+/// the depth of the tree.
+pub(crate) fn search_fanout<T, X, const B: usize>(items: &[T], x: &X) -> Option<usize>
+where
+    T: Borrow<X>,
+    X: Ord + ?Sized,
+{
+    let n = items.len();
+    let mut found = None;
+    let mut k = 0;
+    while first_key::<B>(k) < n {
+        let start = first_key::<B>(k);
+        let node = &items[start..start + keys::<B>(k, n)];
+        // the keys of a node are sorted, so this is the position of the first key `>= x`
+        let i = node.iter().filter(|&v| x > v.borrow()).count();
+        if i < node.len() {
+            found = Some(start + i);
+        }
+        k = child::<B>(k, i);
+    }
+    found
+}
+
+/// Inserts items from the sorted iterator into the subtree rooted at node `k`, in order.
+///
+/// The recursion only goes as deep as the tree, i.e. `log(n) / log(B + 1)` levels.
+fn fill<T, I, const B: usize>(slots: &mut [MaybeUninit<T>], k: usize, iter: &mut I)
+where
+    I: Iterator<Item = T>,
+{
+    let n = slots.len();
+    if first_key::<B>(k) >= n {
+        return;
+    }
+    for i in 0..B {
+        fill::<_, _, B>(slots, child::<B>(k, i), iter);
+        let slot = first_key::<B>(k) + i;
+        if slot >= n {
+            // the node is not full, so the rest of its children are empty as well
+            return;
+        }
+        slots[slot] = MaybeUninit::new(iter.next().unwrap());
+    }
+    fill::<_, _, B>(slots, child::<B>(k, B), iter);
+}
+
+/// Returns the index of the first key of node `k`.
+#[inline(always)]
+fn first_key<const B: usize>(k: usize) -> usize {
+    k.saturating_mul(B)
+}
+
+/// Returns the number of keys in node `k` of a layout of `n` keys, which must be in bounds.
+#[inline(always)]
+fn keys<const B: usize>(k: usize, n: usize) -> usize {
+    (n - first_key::<B>(k)).min(B)
+}
+
+/// Returns the index of the `i`-th child of node `k`, i.e. the one with keys between key `i - 1`
+/// and key `i` of the node.
+#[inline(always)]
+fn child<const B: usize>(k: usize, i: usize) -> usize {
+    k.saturating_mul(B + 1).saturating_add(i + 1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::ALIGN;
+    use alloc::{string::String, vec::Vec};
+    use std::format;
+
+    fn check_fanout<const B: usize>() {
+        // miri is far too slow for the full range
+        let max_n = if cfg!(miri) { 20 } else { 200 };
+        for n in 0..max_n {
+            let expected = (0..n).map(|v| 2 * v).collect::<Vec<_>>();
+            let x = OrderedCollection::<_, B>::from_sorted_iter_fanout(expected.iter().copied());
+            assert_eq!(x.len(), n as usize);
+            assert_eq!(x.node(x.min), expected.first(), "B = {}, n = {}", B, n);
+            assert_eq!(x.node(x.max), expected.last(), "B = {}, n = {}", B, n);
+
+            let mut sorted = x.as_eytzinger_slice().to_vec();
+            sorted.sort_unstable();
+            assert_eq!(sorted, expected);
+
+            for q in 0..2 * n + 2 {
+                let i = expected.partition_point(|&v| v < q);
+                assert_eq!(x.find_gte(q), expected.get(i), "B = {}, n = {}", B, n);
+            }
+        }
+    }
+
+    #[test]
+    fn matches_sorted() {
+        check_fanout::<1>();
+        check_fanout::<2>();
+        check_fanout::<3>();
+        check_fanout::<4>();
+        check_fanout::<7>();
+        check_fanout::<8>();
+        check_fanout::<16>();
+    }
+
+    #[test]
+    fn binary_layout() {
+        for n in 0..100 {
+            let x = OrderedCollection::from_sorted_iter(0..n);
+            let y = OrderedCollection::<_, 1>::from_sorted_iter_fanout(0..n);
+            assert_eq!(x.as_eytzinger_slice(), y.as_eytzinger_slice());
+        }
+    }
+
+    #[test]
+    fn zero_based_descent() {
+        // the fanout search with one key per node descends with the 0-based formula (children at
+        // 2i + 1 and 2i + 2), and must agree with the 1-based one used by the binary layout
+        let max_n = if cfg!(miri) { 20 } else { 200 };
+        for n in 0..max_n {
+            let x = OrderedCollection::from_sorted_iter((0..n).map(|v| 2 * v));
+            let items = x.as_eytzinger_slice();
+            for q in 0..2 * n + 2 {
+                assert_eq!(
+                    search_fanout::<_, _, 1>(items, &q),
+                    crate::search_eytzinger(items, &q)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn cache_line_aligned_nodes() {
+        fn check<const B: usize>() {
+            for n in 0..100 {
+                let x = OrderedCollection::<_, B>::from_sorted_iter_fanout(0..n as u32);
+                assert_eq!(
+                    x.items().as_ptr() as usize % ALIGN,
+                    0,
+                    "B = {}, n = {}",
+                    B,
+                    n
+                );
+                // the first key is at the start of the storage, and there is no unused slot
+                assert_eq!(x.as_eytzinger_slice().as_ptr().cast(), x.items().as_ptr());
+                assert_eq!(x.items().len(), n);
+            }
+        }
+        check::<2>();
+        check::<4>();
+        check::<16>();
+    }
+
+    #[test]
+    fn drops_elements() {
+        let x =
+            OrderedCollection::<_, 3>::from_sorted_iter_fanout((10..30).map(|v| format!("{}", v)));
+        assert_eq!(x.find_gte_ref("155").map(String::as_str), Some("16"));
+    }
+
+    #[test]
+    #[should_panic]
+    fn zero_fanout() {
+        OrderedCollection::<u32, 0>::from_sorted_iter_fanout(0..10);
+    }
+}
@@ -0,0 +1,91 @@
+//! Collections of floats with [ordered-float](https://docs.rs/ordered-float).
+//!
+//! `f32` and `f64` are not [`Ord`], so they can't be stored in an [`OrderedCollection`] as is.
+//! `OrderedFloat` works without any help from this crate, as it orders NaNs after all other values.
+//! For `NotNan`, which rules NaNs out instead, this module adds constructors and lookups that take
+//! plain floats.
+
+use crate::OrderedCollection;
+use alloc::vec::Vec;
+use ordered_float::{FloatCore, FloatIsNan, NotNan};
+
+impl<F: FloatCore> OrderedCollection<NotNan<F>> {
+    /// Construct a new `OrderedCollection` from floats, which must not be NaN.
+    ///
+    /// Returns an error if any of the floats is NaN.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from_floats(vec![3.0, 1.5, 2.25]).unwrap();
+    /// assert_eq!(x.find_gte_float(2.0).map(|v| v.into_inner()), Some(2.25));
+    /// assert_eq!(x.find_gte_float(3.5), None);
+    ///
+    /// assert!(OrderedCollection::from_floats(vec![1.0, f64::NAN]).is_err());
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` holds `isize::MAX` elements or more (see
+    /// [`from_sorted_iter()`](OrderedCollection::from_sorted_iter)).
+    pub fn from_floats(v: Vec<F>) -> Result<Self, FloatIsNan> {
+        let v = v
+            .into_iter()
+            .map(NotNan::new)
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Self::from(v))
+    }
+
+    /// Find the smallest value `v` such that `v >= x`, taking a plain float.
+    ///
+    /// Returns `None` if there is no such `v`, or if `x` is NaN, which no value compares greater
+    /// than or equal to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from_floats(vec![0.5f32, -1.0, 8.0]).unwrap();
+    /// assert_eq!(x.find_gte_float(0.0).map(|v| v.into_inner()), Some(0.5));
+    /// assert_eq!(x.find_gte_float(f32::NAN), None);
+    /// ```
+    pub fn find_gte_float(&self, x: F) -> Option<&NotNan<F>> {
+        NotNan::new(x).ok().and_then(|x| self.find_gte(x))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::vec;
+
+    #[test]
+    fn matches_sorted() {
+        let v = vec![2.5, -0.0, f64::INFINITY, -3.0, 1e-9, 2.5, f64::NEG_INFINITY];
+        let x = OrderedCollection::from_floats(v.clone()).unwrap();
+        let mut sorted = v;
+        sorted.sort_unstable_by(f64::total_cmp);
+        assert_eq!(x.iter().map(|v| v.into_inner()).collect::<Vec<_>>(), sorted);
+
+        for q in [-4.0, -3.0, -1.0, 0.0, 1.0, 2.5, 3.0, f64::INFINITY] {
+            let expected = sorted.iter().find(|&&v| v >= q);
+            assert_eq!(
+                x.find_gte_float(q).map(|v| v.into_inner()),
+                expected.copied()
+            );
+        }
+        assert_eq!(x.find_gte_float(f64::NAN), None);
+    }
+
+    #[test]
+    fn rejects_nan() {
+        assert_eq!(
+            OrderedCollection::from_floats(vec![1.0f32, f32::NAN, 2.0]).unwrap_err(),
+            FloatIsNan
+        );
+        assert!(OrderedCollection::<NotNan<f32>>::from_floats(vec![])
+            .unwrap()
+            .is_empty());
+    }
+}
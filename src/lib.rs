@@ -24,8 +24,10 @@
 //! paper, and what the authors suggested in
 //! https://github.com/patmorin/arraylayout/issues/3#issuecomment-338472755.
 //!
-//! Note that prefetching is *only* enabled with the (non-default) `nightly` feature due to
-//! https://github.com/aweinstock314/prefetch/issues/1. Suggestions for workarounds welcome.
+//! Prefetching uses the stable `_mm_prefetch` intrinsic on x86 and x86_64, and the `prfm`
+//! instruction on AArch64. On other targets, it is *only* enabled with the (non-default) `nightly`
+//! feature, which uses the generic prefetch intrinsic instead, due to
+//! https://github.com/aweinstock314/prefetch/issues/1.
 //!
 //! # Performance
 //!
@@ -45,6 +47,9 @@
 //! recent improvement to [SliceExt::binary_search
 //! performance](https://github.com/rust-lang/rust/pull/45333).
 //!
+//! The `Search (prefetch)` groups compare `find_gte` with and without prefetching, so you can
+//! check whether prefetching pays off on your target.
+//!
 //! Below are [summarized](https://github.com/BurntSushi/cargo-benchcmp) results from an AMD
 //! ThreadRipper 2600X CPU run with:
 //!
@@ -104,28 +109,124 @@
 //!
 //! # Future work
 //!
-//!  - [ ] Implement aligned operation: https://github.com/patmorin/arraylayout/blob/3f20174a2a0ab52c6f37f2ea87d087307f19b5ee/src/eytzinger_array.h#L204
-//!  - [ ] Implement deep prefetching for large `T`: https://github.com/patmorin/arraylayout/blob/3f20174a2a0ab52c6f37f2ea87d087307f19b5ee/src/eytzinger_array.h#L128
+//!  - [x] Implement aligned operation: https://github.com/patmorin/arraylayout/blob/3f20174a2a0ab52c6f37f2ea87d087307f19b5ee/src/eytzinger_array.h#L204
+//!  - [x] Implement deep prefetching for large `T`: https://github.com/patmorin/arraylayout/blob/3f20174a2a0ab52c6f37f2ea87d087307f19b5ee/src/eytzinger_array.h#L128
 //!
 #![deny(missing_docs)]
 #![no_std]
-#![cfg_attr(feature = "nightly", feature(concat_idents))]
 #![cfg_attr(feature = "nightly", feature(core_intrinsics))]
+#![cfg_attr(feature = "nightly", allow(internal_features))]
 
+#[cfg(feature = "alloc")]
 extern crate alloc;
-#[cfg(test)]
+#[cfg(feature = "bytemuck")]
+extern crate bytemuck;
+#[cfg(feature = "ordered-float")]
+extern crate ordered_float;
+#[cfg(feature = "rayon")]
+extern crate rayon;
+#[cfg(feature = "rkyv")]
+extern crate rkyv;
+#[cfg(any(test, feature = "std"))]
 extern crate std;
 
+#[cfg(feature = "rkyv")]
+mod archive;
+mod array;
+#[cfg(feature = "alloc")]
+mod fanout;
+#[cfg(feature = "ordered-float")]
+mod float;
+#[cfg(feature = "rayon")]
+mod par;
+#[cfg(feature = "bytemuck")]
+mod pod;
+#[cfg(feature = "simd")]
+mod simd;
+#[cfg(feature = "alloc")]
+mod storage;
+#[cfg(feature = "rkyv")]
+pub use archive::ArchivedOrderedCollection;
+pub use array::OrderedArray;
+#[cfg(feature = "simd")]
+pub use simd::SimdElement;
+
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
+use core::{borrow::Borrow, marker::PhantomData, mem};
+#[cfg(feature = "alloc")]
 use core::{
-    borrow::Borrow,
-    mem::{self, MaybeUninit},
+    cmp::Ordering,
+    fmt,
+    iter::FromIterator,
+    mem::MaybeUninit,
+    ops::{Index, Range, Sub},
+    ptr,
 };
+#[cfg(feature = "alloc")]
+use storage::Storage;
+
+/// Creates an [`OrderedCollection`] containing the given elements.
+///
+/// This is a shorthand for [`OrderedCollection::from()`] with a `vec!` of the elements, so they
+/// don't need to be sorted, and duplicates are kept.
+///
+/// # Examples
+///
+/// ```
+/// # use ordsearch::{ordered_collection, OrderedCollection};
+/// let x: OrderedCollection<u32> = ordered_collection![8, 1, 4, 2];
+/// assert_eq!(x.find_gte(3), Some(&4));
+/// assert_eq!(x.len(), 4);
+/// ```
+#[cfg(feature = "alloc")]
+#[macro_export]
+macro_rules! ordered_collection {
+    ($($x:expr),* $(,)?) => {
+        $crate::OrderedCollection::from(<[_]>::into_vec($crate::__private::Box::new([$($x),*])))
+    };
+}
+
+/// Not public API, used by [`ordered_collection!`].
+#[cfg(feature = "alloc")]
+#[doc(hidden)]
+pub mod __private {
+    pub use alloc::boxed::Box;
+}
 
 /// A collection of ordered items that can efficiently satisfy queries for nearby elements.
 ///
 /// The most interesting method here is `find_gte`.
 ///
+/// The collection is stored on the heap, so it needs the `alloc` feature, which is enabled by
+/// default. Without it, only [`OrderedArray`] is available.
+///
+/// Elements can be of any type with a total order, like signed integers: they are only compared
+/// with each other and with queries through [`Ord`], and the index computations of the layout never
+/// depend on their values.
+///
+/// Lookups only ever read the collection, so it is `Send` and `Sync` whenever `T` is, and can be
+/// shared between threads with an `Arc`.
+///
+/// A collection of `n` elements allocates room for exactly `n + 1` elements (`n` with a fanout
+/// `B > 1`, see below), aligned to a cache line (see `allocate()`). Methods that change the elements, like `insert` or `extend`, rebuild
+/// the collection into a new allocation of that size. The only exception is `clear`, which keeps
+/// the allocation to be reused by the next elements that fit in it, until `shrink_to_fit` is
+/// called.
+///
+/// A collection may be empty. The layout then only has the uninitialized `[0]`, which is never
+/// returned: every lookup gives `None` (or an empty iterator), and `rank` gives 0.
+///
+/// # Fanout
+///
+/// `B` is the number of keys per node of the tree, and is 1 (a binary tree) unless specified
+/// otherwise. Collections with `B > 1` are experimental: they're built with
+/// [`from_sorted_iter_fanout()`](Self::from_sorted_iter_fanout), where every node holds `B`
+/// consecutive keys and has `B + 1` children. A lookup then scans all the keys of a node and
+/// descends `log(B + 1)` levels at once, so a node that fills a cache line needs only one memory
+/// access. Only [`find_gte()`](Self::find_gte), [`find_gte_ref()`](Self::find_gte_ref) and the
+/// methods that don't depend on the layout are available for them.
+///
 /// # Examples
 ///
 /// ```
@@ -139,18 +240,47 @@ use core::{
 /// assert_eq!(x.find_gte(64), Some(&64));
 /// assert_eq!(x.find_gte(65), None);
 /// ```
-pub struct OrderedCollection<T> {
+#[cfg(feature = "alloc")]
+pub struct OrderedCollection<T, const B: usize = 1> {
     /// Contains all the elements in modified Eytzinger layout
     ///
-    /// This vector is 1-indexed, so the root is at index 1. `[0]` element is intentionally left uninitialized
-    /// to not introduce any additional trait bounds on `T` (like `Copy` or `Default`).
+    /// The layout is 1-indexed, so the root is at index 1. `[0]` element is intentionally left uninitialized
+    /// to not introduce any additional trait bounds on `T` (like `Copy` or `Default`). `[0]` starts at a
+    /// cache line boundary, see [`OrderedCollection::allocate()`]. Use [`OrderedCollection::items()`] to
+    /// access the layout. Layouts with a fanout `B > 1` are 0-based instead, and have no `[0]` to
+    /// leave out (see [`OrderedCollection::FIRST`]).
+    ///
+    /// The binary layout is deliberately not 0-based (children at `2i + 1` and `2i + 2`), although
+    /// that would save the slot for `[0]`:
+    ///
+    ///  - it wouldn't save a single instruction or branch: the descent works on the slice without
+    ///    `[0]`, and the `- 1` is folded into the address computation (see [`eytzinger_descend()`]).
+    ///  - the `2^k` descendants of node `i` at depth `k` are at `2^k * i..2^k * (i + 1)` in the
+    ///    1-based layout, so they start at a cache line boundary and share a single line when they
+    ///    fit in one, which is what [`Prefetch`] relies on. In a 0-based layout they start one
+    ///    element earlier and span two lines, unless the storage starts one element before a
+    ///    boundary, i.e. unless the saved slot comes back as padding.
+    ///
+    /// The fanout layouts have no such prefetching, so they are 0-based. `zero_based_descent` in
+    /// the `fanout` module checks that both descents agree.
     ///
     /// # Safety
-    /// Not under any circumstances `[0]` should be accessed. This is especially important in `Drop`
-    /// implementation and [`eytzinger_walk()`]/[`find_gte()`] functions.
-    items: Vec<MaybeUninit<T>>,
+    /// Not under any circumstances `[0]` of the binary layout should be accessed. This is especially important in
+    /// `Drop` implementation and [`eytzinger_fill()`]/[`find_gte()`] functions.
+    storage: Storage<T>,
+
+    /// Index of the smallest element in the layout, or 0 if the collection is empty
+    min: usize,
+
+    /// Index of the largest element in the layout, or 0 if the collection is empty
+    ///
+    /// Together with `min`, this is cached at construction time, which costs two extra `usize`s per
+    /// collection. It allows [`find_gte()`] to reject queries above the largest element without
+    /// walking the tree.
+    max: usize,
 }
 
+#[cfg(feature = "alloc")]
 impl<T: Ord> From<Vec<T>> for OrderedCollection<T> {
     /// Construct a new `OrderedCollection` from a vector of elements.
     ///
@@ -167,87 +297,187 @@ impl<T: Ord> From<Vec<T>> for OrderedCollection<T> {
     }
 }
 
-/// Insert items from the sorted iterator `I` into `Vec<T>` in complete binary tree order.
-///
-/// Requires `I` to be a sorted iterator.
-/// Requires `Vec<T>` capacity to be set to the number of elements in `iter`.
-/// The length of `Vec<T>` will not be changed by this function.
-fn eytzinger_walk<I, T>(context: &mut (Vec<MaybeUninit<T>>, I), i: usize)
-where
-    I: Iterator<Item = T>,
-{
-    let (v, _) = context;
-    if i >= v.capacity() {
-        return;
+#[cfg(feature = "alloc")]
+impl<T: Ord> FromIterator<T> for OrderedCollection<T> {
+    /// Construct a new `OrderedCollection` from an iterator over elements in arbitrary order.
+    ///
+    /// The elements are collected into a temporary vector and sorted first. If the elements are
+    /// already sorted, use [`OrderedCollection::from_sorted_iter()`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a: OrderedCollection<_> = [42, 89, 7, 12, 3]
+    ///     .iter()
+    ///     .filter(|&&v| v > 5)
+    ///     .map(|&v| v * 2)
+    ///     .collect();
+    /// assert_eq!(a.find_gte(50), Some(&84));
+    /// assert_eq!(a.find_gte(0), Some(&14));
+    /// ```
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::from(iter.into_iter().collect::<Vec<_>>())
     }
+}
 
-    // visit left child
-    eytzinger_walk(context, 2 * i);
+/// Position of a query relative to the elements of an [`OrderedCollection`]
+///
+/// Returned by [`OrderedCollection::locate()`].
+#[cfg(feature = "alloc")]
+#[derive(Debug, PartialEq, Eq)]
+pub enum Match<'a, T> {
+    /// The collection holds an element equal to the query
+    Exact(&'a T),
+    /// The query falls between two adjacent elements, or outside of the elements if either is `None`
+    Between {
+        /// The largest element less than the query
+        lower: Option<&'a T>,
+        /// The smallest element greater than the query
+        upper: Option<&'a T>,
+    },
+    /// The collection is empty
+    Empty,
+}
 
-    // reborrow context
-    let (v, iter) = context;
+/// Marks an iterator as yielding elements in ascending order.
+///
+/// Converting a `Sorted` iterator into an [`OrderedCollection`] skips sorting the elements, same as
+/// [`OrderedCollection::from_sorted_iter()`]. `collect()` can't tell sorted iterators apart from
+/// unsorted ones, so use `into()` or [`From`] instead.
+///
+/// Unlike `from_sorted_iter`, this requires `T: Ord`, and debug builds panic if the elements are
+/// not in ascending order. Release builds don't check the order.
+///
+/// # Examples
+///
+/// ```
+/// # use std::collections::BTreeSet;
+/// # use ordsearch::{OrderedCollection, Sorted};
+/// let s: BTreeSet<_> = vec![42, 89, 7, 12].into_iter().collect();
+/// let a: OrderedCollection<_> = Sorted(s).into();
+/// assert_eq!(a.find_gte(50), Some(&89));
+/// ```
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone)]
+pub struct Sorted<I>(pub I);
 
-    // put data at the root
-    // we know the pointer arithmetics below is safe because we set the Vec's capacity to
-    // the length of the iterator.
-    let value = iter.next().unwrap();
-    unsafe {
-        v.as_mut_ptr().add(i).write(MaybeUninit::new(value));
-    }
+#[cfg(feature = "alloc")]
+impl<I: IntoIterator> IntoIterator for Sorted<I> {
+    type Item = I::Item;
+    type IntoIter = I::IntoIter;
 
-    // visit right child
-    eytzinger_walk(context, 2 * i + 1);
+    fn into_iter(self) -> Self::IntoIter {
+        self.0.into_iter()
+    }
 }
 
-impl<T: Ord> OrderedCollection<T> {
-    /// this computation is a little finicky, so let's walk through it.
-    ///
-    /// we want to prefetch a couple of levels down in the tree from where we are.
-    /// however, we can only fetch one cacheline at a time (assume a line holds 64b).
-    /// we therefore need to find at what depth a single prefetch fetches all the descendants.
-    /// it turns out that, at depth k under some node with index i, the leftmost child is at:
-    ///
-    ///   2^k * i
-    ///
-    /// this follows from the fact that the leftmost immediate child of node i is at 2i by
-    /// recursively expanding i. Note that the original paper uses 0-based indexing (`2i + 1`/`2i + 2`) while we
-    /// use 1-based indexing (`2i`/`2i + 1`). This is because of performance reasons (see:
-    /// [Optimized Eytzinger layout & memory prefetch](https://github.com/jonhoo/ordsearch/pull/27)).
+#[cfg(feature = "alloc")]
+impl<T, I> From<Sorted<I>> for OrderedCollection<T>
+where
+    T: Ord,
+    I: IntoIterator<Item = T>,
+    I::IntoIter: ExactSizeIterator,
+{
+    /// Construct a new `OrderedCollection` from an iterator over sorted elements.
     ///
-    /// If you're curious, the rightmost child is at:
+    /// See [`OrderedCollection::from_sorted_iter()`].
     ///
-    ///   2^k * i + 2^k - 1
+    /// # Panics
     ///
-    /// at depth k, there are 2^k children. we can fit 64/sizeof(T) children in a cacheline, so
-    /// we want to use the depth k that has 64/sizeof(T) children. so, we want:
+    /// In debug builds, panics if the elements are not sorted.
+    fn from(sorted: Sorted<I>) -> Self {
+        if !cfg!(debug_assertions) {
+            return Self::from_sorted_iter(sorted);
+        }
+        match OrderedCollection::try_from_sorted_iter(sorted) {
+            Ok(coll) => coll,
+            Err(e) => panic!("the elements are not sorted: {}", e),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> Default for OrderedCollection<T> {
+    /// Construct an empty `OrderedCollection`.
     ///
-    ///   2^k = 64/sizeof(T)
+    /// # Examples
     ///
-    /// but, we don't actually *need* k. we only ever use 2^k. so, we can just use 64/sizeof(T)
-    /// directly! nice. we call this the multiplier (because it's what we'll multiply i by).
-    const MULTIPLIER: usize = 64 / mem::size_of::<T>();
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::<u32>::default();
+    /// assert!(a.is_empty());
+    /// assert_eq!(a.find_gte(5), None);
+    /// ```
+    fn default() -> Self {
+        Self::from_sorted_iter(Vec::new())
+    }
+}
 
-    /// now we know that multiplier == 2^k, so we're done. right?
-    ///
-    /// right?
+#[cfg(feature = "alloc")]
+impl<T: Ord> Extend<T> for OrderedCollection<T> {
+    /// Adds the elements of an iterator in arbitrary order to the collection.
     ///
-    /// well, only sort of. the prefetch instruction fetches the cache-line that *holds* the
-    /// given memory address. let's denote cache lines with []. what if we have:
+    /// The Eytzinger layout can't be updated in place, so this rebuilds the whole collection. For `n`
+    /// existing and `m` new elements, it takes O(n + m log m) time per call, which makes it a poor fit
+    /// for adding elements one by one. Prefer collecting elements and extending in large batches.
     ///
-    ///   [..., 2^k + 2^k-1] [2^k + 2^k, ...]
+    /// # Examples
     ///
-    /// essentially, we got unlucky with the alignment so that the leftmost child is not sharing
-    /// a cacheline with any of the other items at that level! that's not great. so, instead, we
-    /// prefetch the address that is half-way through the set of children. that way, we ensure
-    /// that we prefetch at least half of the items.
-    const OFFSET: usize = Self::MULTIPLIER / 2;
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut a = OrderedCollection::from(vec![42, 89, 7]);
+    /// a.extend(vec![12, 50]);
+    /// assert_eq!(a.find_gte(45), Some(&50));
+    /// assert_eq!(a.len(), 5);
+    /// ```
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        self.rebuild(|v| {
+            let n = v.len();
+            v.extend(iter);
+            v[n..].sort_unstable();
+            // a stable sort of two sorted runs boils down to merging them in linear time
+            v.sort();
+        });
+    }
+}
+
+/// Insert items from the sorted iterator `I` into the slice in complete binary tree order.
+///
+/// Requires `I` to be a sorted iterator with one element less than the length of the slice.
+/// The slice is 1-indexed, `[0]` is left untouched.
+///
+/// Rather than recursing through the tree (see the reference implementation in tests), we compute the
+/// index of every element from its rank directly with [`layout_index()`]. This keeps the loop tight
+/// and doesn't need any stack space.
+#[cfg(feature = "alloc")]
+fn eytzinger_fill<I, T>(v: &mut [MaybeUninit<T>], mut iter: I)
+where
+    I: Iterator<Item = T>,
+{
+    let n = v.len() - 1;
+    for rank in 0..n {
+        let i = layout_index(n, rank);
+        // SAFETY: layout_index() maps ranks below n into 1..=n, so in-bounds
+        let slot = unsafe { v.get_unchecked_mut(i) };
+        *slot = MaybeUninit::new(iter.next().unwrap());
+    }
+}
 
+#[cfg(feature = "alloc")]
+impl<T> OrderedCollection<T> {
     /// Construct a new `OrderedCollection` from an iterator over sorted elements.
     ///
     /// Note that if the iterator is *not* sorted, no error will be given, but lookups will give
     /// incorrect results. The given iterator must also implement `ExactSizeIterator` so that we
     /// know the size of the lookup array.
     ///
+    /// The order isn't checked even in debug builds, because `T` is not required to implement
+    /// `Ord`: elements may be sorted by a custom order and queried with
+    /// [`find_gte_by()`](Self::find_gte_by). Use
+    /// [`try_from_sorted_iter()`](Self::try_from_sorted_iter) to have the order checked, or
+    /// convert from [`Sorted`] to have it checked in debug builds only.
+    ///
     /// # Examples
     ///
     /// Using an already-sorted iterator:
@@ -290,347 +520,4562 @@ impl<T: Ord> OrderedCollection<T> {
     /// assert_eq!(a.find_gte(50), Some(&89));
     /// ```
     ///
+    /// # Panics
+    ///
+    /// Panics if the iterator yields `isize::MAX` elements or more. Lookups rely on this limit to
+    /// compute child indices (`2 * i + 1`) without overflow.
     pub fn from_sorted_iter<I>(iter: I) -> Self
     where
         I: IntoIterator<Item = T>,
         I::IntoIter: ExactSizeIterator,
     {
         let iter = iter.into_iter();
-        let n = iter.len();
-        // vec with capacity n + 1 because we don't use index 0 and starts with 1
-        let mut context = (Vec::with_capacity(n + 1), iter);
-        eytzinger_walk(&mut context, 1);
-        let (mut items, _) = context;
-
-        // SAFETY: all `n` elements from the iterator was inserted in items.
+        let mut storage = Self::allocate(iter.len());
+        // all `n` elements from the iterator are inserted in 1..=n.
         // [0] is uninitialized, but that's okay since the value type is `MaybeUninit`.
-        unsafe { items.set_len(n + 1) };
+        eytzinger_fill(&mut storage, iter);
 
-        OrderedCollection { items }
+        Self::from_storage(storage)
     }
 
-    /// Construct a new `OrderedCollection` from a slice of elements.
+    /// Construct a new `OrderedCollection` from a vector of sorted elements.
     ///
-    /// Note that the underlying slice will be reordered!
+    /// This builds the same layout as [`from_sorted_iter()`](Self::from_sorted_iter), but the
+    /// other way around: the layout is written front to back, and every node pulls the element of
+    /// the rank that belongs there from `v` (see `layout_rank()`). So the writes are sequential
+    /// and the reads are scattered, which needs random access to the elements. This method only
+    /// exists to compare the two approaches (see the `Construction` benchmarks). So far, scattered
+    /// reads have been slower than scattered writes on every size we measured, so
+    /// `from_sorted_iter` should be preferred otherwise. For the same reason, this method is hidden
+    /// from the documentation and not part of the stable API: it may change or go away in any
+    /// release.
+    ///
+    /// Note that if the vector is *not* sorted, no error will be given, but lookups will give
+    /// incorrect results.
     ///
     /// # Examples
     ///
     /// ```
     /// # use ordsearch::OrderedCollection;
-    /// let mut vals = [42, 89, 7, 12];
-    /// let a = OrderedCollection::from_slice(&mut vals);
-    /// assert_eq!(a.find_gte(50), Some(&&89));
+    /// let a = OrderedCollection::from_sorted_vec(vec![7, 12, 42, 89]);
+    /// assert_eq!(a.find_gte(50), Some(&89));
     /// ```
-    pub fn from_slice(v: &mut [T]) -> OrderedCollection<&T> {
-        v.sort_unstable();
-        OrderedCollection::from_sorted_iter(v.iter())
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` holds `isize::MAX` elements or more (see
+    /// [`from_sorted_iter()`](Self::from_sorted_iter)).
+    #[doc(hidden)]
+    pub fn from_sorted_vec(mut v: Vec<T>) -> Self {
+        let n = v.len();
+        let mut storage = Self::allocate(n);
+        // the elements are moved out below, so `v` must not drop them. Nothing in between can panic.
+        // SAFETY: `0 <= capacity`, and there are no elements to initialize
+        unsafe { v.set_len(0) };
+        let source = v.as_ptr();
+        for (i, slot) in storage.iter_mut().enumerate().skip(1) {
+            // SAFETY: `layout_rank(n, i) < n` for `0 < i <= n`, and every rank is read exactly once since
+            // layout_rank() is a bijection between 1..=n and 0..n
+            *slot = MaybeUninit::new(unsafe { ptr::read(source.add(layout_rank(n, i))) });
+        }
+        Self::from_storage(storage)
     }
 
-    /// Find the smallest value `v` such that `v >= x`.
+    /// Construct a new `OrderedCollection` by copying the elements of an iterator of references in
+    /// sorted order.
     ///
-    /// Returns `None` if there is no such `v`.
+    /// This is the same as `from_sorted_iter(iter.into_iter().copied())`, and builds a collection
+    /// that owns its elements rather than a collection of references. The same caveats as for
+    /// [`from_sorted_iter()`](Self::from_sorted_iter) apply.
     ///
     /// # Examples
     ///
     /// ```
+    /// # use std::collections::BTreeSet;
     /// # use ordsearch::OrderedCollection;
-    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
-    /// assert_eq!(x.find_gte(0), Some(&1));
-    /// assert_eq!(x.find_gte(1), Some(&1));
-    /// assert_eq!(x.find_gte(3), Some(&4));
-    /// assert_eq!(x.find_gte(6), Some(&8));
-    /// assert_eq!(x.find_gte(8), Some(&8));
-    /// assert_eq!(x.find_gte(64), Some(&64));
-    /// assert_eq!(x.find_gte(65), None);
+    /// let s: BTreeSet<u32> = vec![42, 89, 7, 12].into_iter().collect();
+    /// let a: OrderedCollection<u32> = OrderedCollection::from_sorted_iter_copied(s.iter());
+    /// assert_eq!(a.find_gte(50), Some(&89));
     /// ```
-    pub fn find_gte<X>(&self, x: X) -> Option<&T>
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterator yields `isize::MAX` elements or more.
+    pub fn from_sorted_iter_copied<'a, I>(iter: I) -> Self
     where
-        T: Borrow<X>,
-        X: Ord,
+        I: IntoIterator<Item = &'a T>,
+        I::IntoIter: ExactSizeIterator,
+        T: Copy + 'a,
     {
-        let x = x.borrow();
-
-        // Safety: this function should not address self.items[0], because it is not initialized
-        let mut i = 1;
-
-        let mask = prefetch_mask(self.items.len());
-        // the search loop is arithmetic-bound, not memory-bound when using prefetch. So offset part
-        // of prefetch address is intentionally not masked, it allows to do less arithmetic in the loop.
-        // It doesn't affect masking much because `Self::OFFSET` is just half of a cache line.
-        // (see: [Optimized Eytzinger layout & memory prefetch](https://github.com/jonhoo/ordsearch/pull/27))
-        let prefetch_ptr = self.items.as_ptr().wrapping_add(Self::OFFSET);
-
-        while i < self.items.len() {
-            let offset = (Self::MULTIPLIER * i) & mask;
-            do_prefetch(prefetch_ptr.wrapping_add(offset));
+        Self::from_sorted_iter(iter.into_iter().copied())
+    }
 
-            // SAFETY: i < self.items.len(), so in-bounds
-            // SAFETY: 1 <= i, so not [0], so initialized
-            let value = unsafe { self.items.get_unchecked(i).assume_init_ref() }.borrow();
-            // using branchless index update. At the moment compiler cannot reliably tranform
-            // if expressions to branchless instructions like `cmov` and `setb`
-            i = 2 * i + usize::from(x > value);
+    /// Construct a new `OrderedCollection` from an iterator over sorted elements, checking that they
+    /// are actually sorted.
+    ///
+    /// This is the same as [`from_sorted_iter()`](Self::from_sorted_iter), except that it returns
+    /// an error instead of a collection that gives incorrect results if the elements are not sorted.
+    /// The check takes one extra pass over the elements after construction, lookups are not affected.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::try_from_sorted_iter(vec![7, 12, 42, 89]).unwrap();
+    /// assert_eq!(a.find_gte(50), Some(&89));
+    ///
+    /// let err = OrderedCollection::try_from_sorted_iter(vec![7, 42, 12, 89]).unwrap_err();
+    /// assert_eq!(err.index(), 2);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if the iterator yields `isize::MAX` elements or more (see
+    /// [`from_sorted_iter()`](Self::from_sorted_iter)).
+    pub fn try_from_sorted_iter<I>(iter: I) -> Result<Self, UnsortedError>
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+        T: Ord,
+    {
+        let coll = Self::from_sorted_iter(iter);
+        // the walk consumes the iterator in order, so ranks correspond to positions in the iterator
+        match (1..coll.len()).find(|&rank| coll.by_rank(rank - 1) > coll.by_rank(rank)) {
+            Some(index) => Err(UnsortedError { index }),
+            None => Ok(coll),
         }
-
-        // Because the branchless loop navigates the tree until we reach a leaf node regardless of whether
-        // the value is found or not, we now need to decode the found value index, if any.
-        //
-        // To understand how this works, it is useful to think of the index as a binary number.
-        // The index update strategy always multiplies the index by 2 (which can be seen as `i <<= 1`)
-        // and then adds 1 (which can be seen as `i |= 1`) if the value is greater than the current node value.
-        // So, we can interpret the bits in index as a history of turns we made in the tree: a 0 bit means
-        // we went left, a 1 bit means we went right.
-        //
-        // Another important observation is that when we find the target value, we make a left turn
-        // and the corresponding bit in the index will be 0. More importantly, all subsequent bits
-        // will be 1, because after we made a left turn we ended up in a subtree where all values
-        // are less than the target value.
-        //
-        // Therefore, to decode the index we need to:
-        //   1. get rid of all trailing 1 bits (dummy turns we made after we found the target value)
-        //   2. get rid of one more bit to restore the index state before we made a left turn at the target element
-        //   3. check if the resulting index is greater than 0 (0 means the target value is not in the tree)
-        i >>= i.trailing_ones() + 1;
-        // SAFETY: i < self.items.len(), so in-bounds
-        // SAFETY: 1 <= i, so not [0], so initialized
-        (i > 0).then(|| unsafe { self.items.get_unchecked(i).assume_init_ref() })
     }
 
-    /// Iterator over elements of a collection.
+    /// Construct a new `OrderedCollection` from an iterator over elements sorted by `cmp`.
     ///
-    /// It yields all items in an unspecified order.
+    /// This is the same as [`from_sorted_iter()`](Self::from_sorted_iter), which never compares
+    /// the elements, except that debug builds check the order with `cmp` and panic if the elements
+    /// are not sorted. The comparator is not stored in the collection (that would need another type
+    /// parameter on the collection), so queries have to pass it again to
+    /// [`find_gte_by()`](Self::find_gte_by). To keep a custom order in the type instead, wrap the
+    /// elements in [`core::cmp::Reverse`] or a newtype that implements [`Ord`].
     ///
     /// # Examples
     ///
     /// ```
     /// # use ordsearch::OrderedCollection;
-    /// let expected = vec![1, 2, 3, 4, 5];
-    /// let coll = OrderedCollection::from(expected.clone());
-    /// let mut values: Vec<_> = coll.iter().copied().collect();
-    /// values.sort();
-    /// assert_eq!(values, expected);
+    /// let desc = |a: &u32, b: &u32| b.cmp(a);
+    /// let x = OrderedCollection::from_sorted_iter_by(vec![64, 16, 4, 1], desc);
+    /// assert_eq!(x.find_gte_by(20, desc), Some(&16));
     /// ```
-    pub fn iter(&self) -> Iter<'_, T> {
-        Iter { coll: self, idx: 0 }
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the elements are not sorted by `cmp`. Panics if the iterator
+    /// yields `isize::MAX` elements or more (see [`from_sorted_iter()`](Self::from_sorted_iter)).
+    pub fn from_sorted_iter_by<I, F>(iter: I, cmp: F) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+        F: Fn(&T, &T) -> Ordering,
+    {
+        let coll = Self::from_sorted_iter(iter);
+        debug_assert!(
+            (1..coll.len())
+                .all(|rank| cmp(coll.by_rank(rank - 1), coll.by_rank(rank)) != Ordering::Greater),
+            "the elements are not sorted"
+        );
+        coll
     }
-}
 
-impl<'a, T: Ord> IntoIterator for &'a OrderedCollection<T> {
-    type Item = &'a T;
-    type IntoIter = Iter<'a, T>;
-
-    fn into_iter(self) -> Self::IntoIter {
-        self.iter()
+    /// Construct a new `OrderedCollection` from an iterator over elements sorted by the key that
+    /// `key` extracts.
+    ///
+    /// Like [`from_sorted_iter_by()`](Self::from_sorted_iter_by), the key is only used to check the
+    /// order in debug builds, and queries pass it again to
+    /// [`find_gte_by_key()`](Self::find_gte_by_key).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from_sorted_iter_by_key(vec![(1, 'a'), (4, 'b')], |&(ts, _)| ts);
+    /// assert_eq!(x.find_gte_by_key(2, |&(ts, _)| ts), Some(&(4, 'b')));
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// In debug builds, panics if the elements are not sorted by their keys. Panics if the
+    /// iterator yields `isize::MAX` elements or more (see
+    /// [`from_sorted_iter()`](Self::from_sorted_iter)).
+    pub fn from_sorted_iter_by_key<I, K, F>(iter: I, key: F) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        I::IntoIter: ExactSizeIterator,
+        F: Fn(&T) -> K,
+        K: Ord,
+    {
+        Self::from_sorted_iter_by(iter, |a, b| key(a).cmp(&key(b)))
     }
-}
-
-impl<T> IntoIterator for OrderedCollection<T> {
-    type Item = T;
-    type IntoIter = alloc::vec::IntoIter<T>;
 
-    fn into_iter(self) -> Self::IntoIter {
-        Vec::from(self).into_iter()
+    /// Construct a new `OrderedCollection` from elements that are only [`PartialOrd`], like floats,
+    /// checking that they can all be compared.
+    ///
+    /// Returns an error if an element can't be compared with itself, like a NaN (or a newtype
+    /// around a float whose `PartialOrd` forwards to the float). Sorting a NaN with an `Ord` that
+    /// ignores it would otherwise silently give a collection with incorrect results. The elements
+    /// that pass the check must be totally ordered by [`PartialOrd`], as floats without NaNs are.
+    ///
+    /// `T` doesn't need to be [`Ord`], so `f32` and `f64` can be used directly, and searched with
+    /// [`find_gte_by()`](Self::find_gte_by). Queries must compare with `partial_cmp` as well, the
+    /// order the elements are sorted by. Other orders may disagree with it: `f64::total_cmp` puts
+    /// `-0.0` before `0.0`, while `partial_cmp` considers them equal and leaves them in any order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::try_from_partial_ord(vec![2.5, -1.0, 0.5]).unwrap();
+    /// let by_float = |v: &f64, x: &f64| v.partial_cmp(x).unwrap();
+    /// assert_eq!(a.find_gte_by(0.0, by_float), Some(&0.5));
+    ///
+    /// let err = OrderedCollection::try_from_partial_ord(vec![2.5, f64::NAN, 0.5]).unwrap_err();
+    /// assert_eq!(err.index(), 1);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `v` holds `isize::MAX` elements or more (see
+    /// [`from_sorted_iter()`](Self::from_sorted_iter)).
+    pub fn try_from_partial_ord(mut v: Vec<T>) -> Result<Self, IncomparableError>
+    where
+        T: PartialOrd,
+    {
+        if let Some(index) = v
+            .iter()
+            .position(|value| value.partial_cmp(value).is_none())
+        {
+            return Err(IncomparableError { index });
+        }
+        // never falls back to `Equal` if the remaining elements are totally ordered
+        v.sort_unstable_by(|a, b| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+        Ok(Self::from_sorted_iter(v))
     }
-}
-
-/// Immutable iterator over elements in a [`OrderedCollection`]
-///
-/// Created by [`OrderedCollection::iter()`].
-pub struct Iter<'a, T> {
-    coll: &'a OrderedCollection<T>,
-    idx: usize,
-}
 
-impl<'a, T> Iterator for Iter<'a, T> {
-    type Item = &'a T;
-
-    fn next(&mut self) -> Option<Self::Item> {
-        self.idx += 1;
-        if self.idx < self.coll.items.len() {
-            let value = &self.coll.items[self.idx];
-            // SAFETY: i > 0, so only initialized items are accessed
-            // SAFETY: i < self.coll.items.len() so no out-of-bounds access
-            Some(unsafe { value.assume_init_ref() })
+    /// Construct a new `OrderedCollection` from storage that holds elements in Eytzinger layout.
+    fn from_storage(storage: Storage<T>) -> Self {
+        let n = storage.len() - 1;
+        let (min, max) = if n > 0 {
+            (layout_index(n, 0), layout_index(n, n - 1))
         } else {
-            None
-        }
+            (0, 0)
+        };
+        OrderedCollection { storage, min, max }
     }
-}
 
-impl<T> From<OrderedCollection<T>> for Vec<T> {
-    /// Converts all elemenets into a new [`Vec`] in unspecified order
+    /// Construct a new `OrderedCollection` from a slice of elements.
+    ///
+    /// Note that the underlying slice will be reordered!
     ///
     /// # Examples
     ///
     /// ```
     /// # use ordsearch::OrderedCollection;
-    /// let x = vec![1, 2, 3, 4, 5];
-    /// let coll = OrderedCollection::from(x.clone());
-    /// let mut values = Vec::from(coll);
-    /// values.sort();
-    /// assert_eq!(values, x);
+    /// let mut vals = [42, 89, 7, 12];
+    /// let a = OrderedCollection::from_slice(&mut vals);
+    /// assert_eq!(a.find_gte(50), Some(&&89));
     /// ```
-    fn from(mut value: OrderedCollection<T>) -> Self {
-        assert!(!value.items.is_empty());
-
-        let mut items = mem::take(&mut value.items);
-        items.swap_remove(0);
-        // SAFETY: 0-th element already removed, so all initialized
-        unsafe { mem::transmute(items) }
+    pub fn from_slice(v: &mut [T]) -> OrderedCollection<&T>
+    where
+        T: Ord,
+    {
+        v.sort_unstable();
+        OrderedCollection::from_sorted_iter(v.iter())
     }
-}
 
-impl<T> Drop for OrderedCollection<T> {
-    fn drop(&mut self) {
-        // SAFETY: all elements beyond [0] are initialized, so can be dropped (which .truncate(1) will do)
-        // at the end of drop(), items will hold a single `MaybeUninit<T>` (`[0]`), which is uninitialized.
-        // when the `Vec` is dropped, it will then drop `[0]`, but that's fine since dropping an uninitialized
-        // `MaybeUninit<T>` doesn't call `T::drop` and is sound.
-        let items: &mut Vec<T> = unsafe { mem::transmute(&mut self.items) };
-        items.truncate(1);
+    /// Construct a new `OrderedCollection` from a slice of sorted elements.
+    ///
+    /// Unlike [`from_slice()`](Self::from_slice), the slice is left untouched and the elements are
+    /// cloned into the collection. The slice must already be sorted: as with
+    /// [`from_sorted_iter()`](Self::from_sorted_iter), no error will be given if it isn't, but
+    /// lookups will give incorrect results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let vals = [7, 12, 42, 89];
+    /// let a = OrderedCollection::from_sorted_slice(&vals);
+    /// assert_eq!(a.find_gte(50), Some(&89));
+    /// ```
+    pub fn from_sorted_slice(v: &[T]) -> Self
+    where
+        T: Clone,
+    {
+        Self::from_sorted_iter(v.iter().cloned())
+    }
+
+    /// Construct a new `OrderedCollection` from elements that are already in Eytzinger layout.
+    ///
+    /// This skips the reordering done by [`from_sorted_iter()`](Self::from_sorted_iter), and is
+    /// meant for restoring a layout that was persisted elsewhere. The layout is trusted as is, so
+    /// `items` must satisfy the following:
+    ///
+    ///  - `items[i]` holds node `i + 1` of the (1-based) Eytzinger layout. The internal sentinel
+    ///    slot is not part of `items`, so a collection of `n` elements takes exactly `n` items. This
+    ///    is the layout returned by [`as_eytzinger_slice()`](Self::as_eytzinger_slice), so the two
+    ///    round-trip.
+    ///  - the items are a valid Eytzinger permutation of a sorted sequence, i.e. what
+    ///    [`from_sorted_iter()`](Self::from_sorted_iter) would produce for that sequence.
+    ///
+    /// This function is safe: the sentinel is allocated here, and any `Vec` fills the layout
+    /// completely, so memory safety doesn't depend on the contents of `items`. As with an unsorted
+    /// iterator, no error will be given if `items` is not a valid layout, but lookups will give
+    /// incorrect results. It is never undefined behavior.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// // the Eytzinger layout of [1, 2, 3, 4, 5, 6, 7]
+    /// let a = OrderedCollection::from_eytzinger_vec(vec![4, 2, 6, 1, 3, 5, 7]);
+    /// assert_eq!(a.find_gte(5), Some(&5));
+    /// assert_eq!(a.iter().copied().collect::<Vec<_>>(), [1, 2, 3, 4, 5, 6, 7]);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if `items` holds `isize::MAX` elements or more (see
+    /// [`from_sorted_iter()`](Self::from_sorted_iter)).
+    pub fn from_eytzinger_vec(items: Vec<T>) -> Self {
+        let mut storage = Self::allocate(items.len());
+        for (slot, value) in storage[1..].iter_mut().zip(items) {
+            *slot = MaybeUninit::new(value);
+        }
+        Self::from_storage(storage)
+    }
+
+    /// Returns the element at index `i` of the internal layout.
+    ///
+    /// `i` is a 0-based position in [`as_eytzinger_slice()`](Self::as_eytzinger_slice), not the
+    /// 1-based node index used internally, so `raw(0)` is the root of the layout and `i` ranges
+    /// over `0..len()`. This is *not* the `i`-th smallest element, which is what indexing the collection with
+    /// `coll[i]` or [`get()`](Self::get) returns. It's the same as `as_eytzinger_slice()[i]`, and
+    /// pairs with [`find_gte_index()`](Self::find_gte_index) for keeping data associated with the
+    /// elements in a separate array in layout order. The layout is an implementation detail (see
+    /// [`as_eytzinger_slice()`](Self::as_eytzinger_slice)).
+    ///
+    /// # Panics
+    ///
+    /// Panics if `i >= len()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 3, 4, 5, 6, 7]);
+    /// assert_eq!(x.raw(0), &4);
+    /// assert_eq!(x[0], 1);
+    /// let i = x.find_gte_index(6).unwrap();
+    /// assert_eq!(x.raw(i), &6);
+    /// ```
+    pub fn raw(&self, i: usize) -> &T {
+        &self.as_eytzinger_slice()[i]
+    }
+
+    /// Find the position of the smallest value `v` such that `v >= x` in the internal layout.
+    ///
+    /// The returned index is a 0-based position in [`as_eytzinger_slice()`](Self::as_eytzinger_slice),
+    /// not the 1-based node index used internally (that one is off by one, because of the sentinel
+    /// slot the slice leaves out). This allows to keep data associated with the elements in a
+    /// separate array of `len()` items in the same order, and to look the element up with
+    /// [`raw()`](Self::raw). The
+    /// layout is an implementation detail, so the index is only meaningful for this collection (or
+    /// one with the same number of elements built by the same version of this crate). Returns `None`
+    /// if there is no such `v`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// let i = x.find_gte_index(3).unwrap();
+    /// assert_eq!(x.as_eytzinger_slice()[i], 4);
+    /// assert_eq!(x.find_gte_index(9), None);
+    /// ```
+    pub fn find_gte_index<X>(&self, x: X) -> Option<usize>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        if self.node(self.max).map_or(true, |max| x > max.borrow()) {
+            return None;
+        }
+        search_eytzinger(self.as_eytzinger_slice(), x)
+    }
+
+    /// Find the position of the smallest value `v` such that `v >= x`, writing it into `out_index`.
+    ///
+    /// This is [`find_gte_index()`](Self::find_gte_index) in a form that is easier to expose over FFI:
+    /// returns `true` and writes the index of `v` in [`as_eytzinger_slice()`](Self::as_eytzinger_slice)
+    /// into `out_index` if there is such `v`. The index is 0-based and follows the internal layout,
+    /// not the sorted order, so the value can be fetched with [`raw()`](Self::raw). Returns `false` and
+    /// leaves `out_index` unchanged otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// let mut i = 0;
+    /// assert!(x.find_gte_raw(3, &mut i));
+    /// assert_eq!(x.raw(i), &4);
+    /// assert!(!x.find_gte_raw(9, &mut i));
+    /// ```
+    pub fn find_gte_raw<X>(&self, x: X, out_index: &mut usize) -> bool
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        match self.find_gte_index(x) {
+            Some(i) => {
+                *out_index = i;
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Find the smallest value `v` such that `v >= x`, returning a mutable reference to it.
+    ///
+    /// This allows to update data stored alongside the keys of the elements without rebuilding the
+    /// collection. Returns `None` if there is no such `v`.
+    ///
+    /// **The caller must not change the order of the element relative to the others.** The layout
+    /// is not updated, so if the element no longer compares the same way, lookups will give
+    /// incorrect results. This can't cause undefined behavior, but there is no way to detect it
+    /// either. Only mutate the parts of `T` that its [`Ord`] implementation ignores.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// # use std::cmp::Ordering;
+    /// #[derive(PartialEq, Eq)]
+    /// struct Record {
+    ///     key: u32,
+    ///     hits: u32,
+    /// }
+    /// impl PartialOrd for Record {
+    ///     fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    ///         Some(self.cmp(other))
+    ///     }
+    /// }
+    /// impl Ord for Record {
+    ///     fn cmp(&self, other: &Self) -> Ordering {
+    ///         self.key.cmp(&other.key)
+    ///     }
+    /// }
+    ///
+    /// let mut x = OrderedCollection::from(vec![
+    ///     Record { key: 10, hits: 0 },
+    ///     Record { key: 20, hits: 0 },
+    /// ]);
+    /// x.find_gte_mut(Record { key: 15, hits: 0 }).unwrap().hits += 1;
+    /// assert_eq!(x.find_gte(Record { key: 11, hits: 0 }).unwrap().hits, 1);
+    /// ```
+    pub fn find_gte_mut<X>(&mut self, x: X) -> Option<&mut T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let i = self.find_gte_index(x)?;
+        // SAFETY: find_gte_index() returns an index into `as_eytzinger_slice()`, which starts at
+        // [1] of the storage, so the element is in bounds and initialized
+        Some(unsafe { self.storage.get_unchecked_mut(i + 1).assume_init_mut() })
+    }
+
+    /// Find the smallest value `v` such that `v >= x`, or the largest value if there is no such `v`.
+    ///
+    /// This clamps `x` to the elements of the collection: it returns the same as
+    /// [`find_gte()`](Self::find_gte), except that queries above all elements give the largest one
+    /// instead of `None`. Returns `None` only if the collection is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// assert_eq!(x.find_gte_or_last(3), Some(&4));
+    /// assert_eq!(x.find_gte_or_last(9), Some(&8));
+    /// assert_eq!(OrderedCollection::<u32>::default().find_gte_or_last(9), None);
+    /// ```
+    pub fn find_gte_or_last<X>(&self, x: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        // the largest element is cached, so falling back to it doesn't need another walk
+        self.find_gte(x).or_else(|| self.node(self.max))
+    }
+
+    /// Find the smallest value `v` such that `v >= x` without prefetching memory.
+    ///
+    /// Returns the same results as [`find_gte()`](Self::find_gte), which prefetches the nodes a few
+    /// levels down the tree while comparing the current one (on x86, or with the `nightly` feature).
+    /// This method only exists to measure the impact of prefetching on a given target, `find_gte`
+    /// should be preferred otherwise. It is hidden from the documentation and not part of the stable
+    /// API.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.find_gte_without_prefetch(3), Some(&4));
+    /// assert_eq!(x.find_gte_without_prefetch(65), None);
+    /// ```
+    #[doc(hidden)]
+    pub fn find_gte_without_prefetch<X>(&self, x: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        if self.node(self.max).map_or(true, |max| x > max.borrow()) {
+            return None;
+        }
+        let i = self.walk(|_, _, _| {}, |value| x > value.borrow());
+        self.node(last_left_turn(i))
+    }
+
+    /// Find the smallest value `v` such that `v >= x`, prefetching nodes `LEVELS` levels ahead.
+    ///
+    /// Returns the same results as [`find_gte()`](Self::find_gte), which prefetches as many levels
+    /// ahead as fit into a single cache line (or a few more for large `T`). Depending on the memory
+    /// latency of the target, prefetching further ahead may hide more of it, at the cost of fetching
+    /// `2^LEVELS` nodes per step, most of which are never visited. `LEVELS` only changes which
+    /// addresses are prefetched, never which elements are compared. It must be less than
+    /// `usize::BITS`.
+    ///
+    /// Prefetching is only enabled on x86 or with the `nightly` feature. This method exists to
+    /// benchmark the distance against `find_gte`, so it is hidden from the documentation and not part
+    /// of the stable API.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.find_gte_with_prefetch_levels::<6, _>(3), Some(&4));
+    /// assert_eq!(x.find_gte_with_prefetch_levels::<6, _>(65), None);
+    /// ```
+    #[doc(hidden)]
+    pub fn find_gte_with_prefetch_levels<const LEVELS: u32, X>(&self, x: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        if self.node(self.max).map_or(true, |max| x > max.borrow()) {
+            return None;
+        }
+        let i = self.walk(Prefetch::<T>::prefetch_levels::<LEVELS>, |value| {
+            x > value.borrow()
+        });
+        self.node(last_left_turn(i))
+    }
+
+    /// Find the smallest value `v` such that `v >= x`, scanning the last `LEVELS` levels of the tree
+    /// linearly instead of descending through them.
+    ///
+    /// Returns the same results as [`find_gte()`](Self::find_gte). The search descends branch-free
+    /// until it reaches the root of a subtree spanning the last `LEVELS` levels (up to
+    /// `2^LEVELS - 1` elements), and then counts how many elements of that subtree are smaller than
+    /// `x`. Every level of a subtree is contiguous in memory, so the scan walks `LEVELS` short runs
+    /// of elements with no dependency between the comparisons. The count is the position of the
+    /// result within the subtree in sorted order, which is mapped back to its index in the layout.
+    ///
+    /// The scan does more comparisons than the descent, but doesn't wait for each one before loading
+    /// the next element. Whether that pays off depends on `T` and the target, so this method exists
+    /// to measure it, and is hidden from the documentation and not part of the stable API. With
+    /// `LEVELS = 0` it is the same as `find_gte`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.find_gte_linear_tail::<2, _>(3), Some(&4));
+    /// assert_eq!(x.find_gte_linear_tail::<2, _>(64), Some(&64));
+    /// assert_eq!(x.find_gte_linear_tail::<2, _>(65), None);
+    /// ```
+    #[doc(hidden)]
+    pub fn find_gte_linear_tail<const LEVELS: u32, X>(&self, x: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        if self.node(self.max).map_or(true, |max| x > max.borrow()) {
+            return None;
+        }
+
+        let nodes = self.as_eytzinger_slice();
+        let n = nodes.len();
+        let base = nodes.as_ptr().wrapping_sub(1);
+        let mask = prefetch_mask(n + 1);
+        // depth of the last level, and of the root of the subtree that is scanned
+        let height = usize::BITS - 1 - n.leading_zeros();
+        let top = (height + 1).saturating_sub(LEVELS);
+
+        // the last level may be partial, which only matters when `LEVELS` is 0
+        let stop = (1 << top).min(n + 1);
+        let mut i = 1;
+        while i < stop {
+            Prefetch::<T>::prefetch(base, i, mask);
+            // SAFETY: 1 <= i < stop <= n + 1, so in-bounds
+            let value = unsafe { nodes.get_unchecked(i - 1) };
+            i = 2 * i + usize::from(x > value.borrow());
+        }
+
+        // the nodes of the subtree rooted at `i` that are `j` levels down are `i << j..(i + 1) << j`.
+        // The last level of the tree is filled from the left, so the subtree is complete as well.
+        let mut size = 0;
+        let mut smaller = 0;
+        for j in 0..LEVELS {
+            let start = i << j;
+            if start > n {
+                break;
+            }
+            let end = ((i + 1) << j).min(n + 1);
+            for k in start..end {
+                // SAFETY: 1 <= start <= k < end <= n + 1, so in-bounds
+                let value = unsafe { nodes.get_unchecked(k - 1) };
+                smaller += usize::from(x > value.borrow());
+            }
+            size += end - start;
+        }
+
+        if smaller < size {
+            // the `smaller`-th element of the subtree in sorted order
+            let q = layout_index(size, smaller);
+            let depth = usize::BITS - 1 - q.leading_zeros();
+            self.node((i << depth) + q - (1 << depth))
+        } else {
+            // everything in the subtree is smaller than `x`, as if the walk only turned right from `i`
+            self.node(last_left_turn(i))
+        }
+    }
+
+    /// Find the smallest value `v` such that `v >= x` using a conventional branching search.
+    ///
+    /// Returns the same results as [`find_gte()`](Self::find_gte). `find_gte` avoids branches in the
+    /// search loop, so its running time does not depend on how well the CPU predicts the path through
+    /// the tree. When queries are highly skewed, the branches in this method are predicted well, and it
+    /// may be faster than the branch-free version. Benchmark both on your workload to pick one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.find_gte_branchy(0), Some(&1));
+    /// assert_eq!(x.find_gte_branchy(3), Some(&4));
+    /// assert_eq!(x.find_gte_branchy(64), Some(&64));
+    /// assert_eq!(x.find_gte_branchy(65), None);
+    /// ```
+    pub fn find_gte_branchy<X>(&self, x: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        let items = self.items();
+
+        // Safety: this function should not address items[0], because it is not initialized
+        let mut i = 1;
+        let mut found = 0;
+        while i < items.len() {
+            // SAFETY: i < items.len(), so in-bounds
+            // SAFETY: 1 <= i, so not [0], so initialized
+            let value = unsafe { items.get_unchecked(i).assume_init_ref() };
+            if x > value.borrow() {
+                i = 2 * i + 1;
+            } else {
+                found = i;
+                i *= 2;
+            }
+        }
+        self.node(found)
+    }
+
+    /// Find the smallest value `v` such that `v >= x` using vector instructions.
+    ///
+    /// Returns the same results as [`find_gte()`](Self::find_gte), but resolves three levels of the
+    /// tree at a time by comparing the query against all seven elements on those levels at once. This
+    /// reduces the number of dependent loads during the search. Only available for the element types
+    /// implementing [`SimdElement`] with the (non-default) `simd` feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from((0..100u32).map(|v| v * 2).collect::<Vec<_>>());
+    /// assert_eq!(x.find_gte_simd(0), Some(&0));
+    /// assert_eq!(x.find_gte_simd(41), Some(&42));
+    /// assert_eq!(x.find_gte_simd(199), None);
+    /// ```
+    #[cfg(feature = "simd")]
+    pub fn find_gte_simd(&self, x: T) -> Option<&T>
+    where
+        T: SimdElement,
+    {
+        let items = self.items();
+        let len = items.len();
+        // Safety: this function should not address items[0], because it is not initialized
+        let mut i = 1;
+
+        // the three levels below i are complete as long as the last grandchild is in bounds:
+        // 4 * i + 3 < len, but written in a way that can't overflow
+        let limit = len.saturating_sub(4) / 4;
+        while i <= limit {
+            // SAFETY: 4 * i + 3 < len, so all the accessed indices are in-bounds
+            // SAFETY: 1 <= i, so none of them is [0], so all are initialized
+            let count = unsafe {
+                let node = |j: usize| items.get_unchecked(j).assume_init_read();
+                let grandchildren = &*items.as_ptr().add(4 * i).cast::<[T; 4]>();
+                usize::from(x > node(i))
+                    + usize::from(x > node(2 * i))
+                    + usize::from(x > node(2 * i + 1))
+                    + T::count_lt(x, grandchildren)
+            };
+            i = 8 * i + count;
+        }
+
+        // the bottom of the tree is incomplete, so we finish the search one level at a time
+        while i < len {
+            // SAFETY: i < items.len(), so in-bounds
+            // SAFETY: 1 <= i, so not [0], so initialized
+            let value = unsafe { items.get_unchecked(i).assume_init_read() };
+            i = 2 * i + usize::from(x > value);
+        }
+
+        self.node(last_left_turn(i))
+    }
+
+    /// Find the largest value `v` such that `v <= x`.
+    ///
+    /// Returns `None` if there is no such `v`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.find_lte(0), None);
+    /// assert_eq!(x.find_lte(1), Some(&1));
+    /// assert_eq!(x.find_lte(3), Some(&2));
+    /// assert_eq!(x.find_lte(6), Some(&4));
+    /// assert_eq!(x.find_lte(8), Some(&8));
+    /// assert_eq!(x.find_lte(64), Some(&64));
+    /// assert_eq!(x.find_lte(65), Some(&64));
+    /// ```
+    pub fn find_lte<X>(&self, x: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        let i = self.descend(|value| x >= value.borrow());
+
+        // This is the mirror image of `find_gte`: we turn right on every value that is not greater
+        // than the target, so the answer is the node where we made the last right turn. All
+        // subsequent turns are left turns (trailing 0 bits) which we need to strip along with the
+        // right turn itself.
+        self.node(last_right_turn(i))
+    }
+
+    /// Find the smallest value `v` such that `v > x`.
+    ///
+    /// Returns `None` if there is no such `v`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// assert_eq!(x.find_gt(0), Some(&1));
+    /// assert_eq!(x.find_gt(2), Some(&4));
+    /// assert_eq!(x.find_gt(3), Some(&4));
+    /// assert_eq!(x.find_gt(8), None);
+    /// ```
+    pub fn find_gt<X>(&self, x: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        // same as `find_gte`, but we also skip over values equal to the target
+        let i = self.descend(|value| x >= value.borrow());
+        self.node(last_left_turn(i))
+    }
+
+    /// Find the largest value `v` such that `v < x`.
+    ///
+    /// Returns `None` if there is no such `v`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// assert_eq!(x.find_lt(1), None);
+    /// assert_eq!(x.find_lt(2), Some(&1));
+    /// assert_eq!(x.find_lt(3), Some(&2));
+    /// assert_eq!(x.find_lt(9), Some(&8));
+    /// ```
+    pub fn find_lt<X>(&self, x: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        // same as `find_lte`, but we do not step over values equal to the target
+        let i = self.descend(|value| x > value.borrow());
+        self.node(last_right_turn(i))
+    }
+
+    /// Find the smallest value `v` such that `v >= x` according to the comparator `cmp`.
+    ///
+    /// `cmp` compares an element of the collection with the query, similar to
+    /// [`slice::binary_search_by`]. Returns `None` if there is no such `v`.
+    ///
+    /// The comparator is not stored in the collection, so it is up to the caller to make sure that
+    /// the elements were given to [`from_sorted_iter()`](Self::from_sorted_iter) in the order
+    /// defined by `cmp` ([`from_sorted_iter_by()`](Self::from_sorted_iter_by) checks this in debug
+    /// builds). Otherwise, lookups will give incorrect results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from_sorted_iter(vec![64, 32, 16, 8, 4, 2, 1]);
+    /// let desc = |v: &u32, x: &u32| x.cmp(v);
+    /// assert_eq!(x.find_gte_by(65, desc), Some(&64));
+    /// assert_eq!(x.find_gte_by(64, desc), Some(&64));
+    /// assert_eq!(x.find_gte_by(6, desc), Some(&4));
+    /// assert_eq!(x.find_gte_by(0, desc), None);
+    /// ```
+    ///
+    /// Querying a collection of `String`s with a `&str`, without allocating:
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec!["apple".to_string(), "hello".to_string(), "world".to_string()]);
+    /// let by_str = |v: &String, x: &&str| v.as_str().cmp(x);
+    /// assert_eq!(x.find_gte_by("hello", by_str).map(String::as_str), Some("hello"));
+    /// assert_eq!(x.find_gte_by("help", by_str).map(String::as_str), Some("world"));
+    /// assert_eq!(x.find_gte_by("zebra", by_str), None);
+    /// ```
+    pub fn find_gte_by<X, F>(&self, x: X, cmp: F) -> Option<&T>
+    where
+        F: Fn(&T, &X) -> Ordering,
+    {
+        let i = self.descend(|value| cmp(value, &x) == Ordering::Less);
+        self.node(last_left_turn(i))
+    }
+
+    /// Find the element `v` with the smallest key such that `key(v) >= k`.
+    ///
+    /// Returns `None` if there is no such `v`. This is similar to [`slice::binary_search_by_key`] and
+    /// requires the elements to be given to [`from_sorted_iter()`](Self::from_sorted_iter) sorted by
+    /// their keys ([`from_sorted_iter_by_key()`](Self::from_sorted_iter_by_key) checks this in debug
+    /// builds).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from_sorted_iter(vec![(1, 'a'), (4, 'b'), (9, 'c')]);
+    /// assert_eq!(x.find_gte_by_key(2, |&(ts, _)| ts), Some(&(4, 'b')));
+    /// assert_eq!(x.find_gte_by_key(9, |&(ts, _)| ts), Some(&(9, 'c')));
+    /// assert_eq!(x.find_gte_by_key(10, |&(ts, _)| ts), None);
+    /// ```
+    pub fn find_gte_by_key<K, F>(&self, k: K, key: F) -> Option<&T>
+    where
+        F: Fn(&T) -> K,
+        K: Ord,
+    {
+        self.find_gte_by(k, |value, k| key(value).cmp(k))
+    }
+
+    /// Find the smallest element `v` (in sorted order) for which `pred(v)` is `true`.
+    ///
+    /// Returns `None` if there is no such `v`. This generalizes [`find_gte()`](Self::find_gte), which
+    /// is the same as `find_first_true(|v| v >= x)`, to orders that can't be expressed with a single
+    /// query value.
+    ///
+    /// `pred` must be monotone over the sorted elements: `false` for a (possibly empty) prefix of them
+    /// and `true` for the rest. Otherwise, no error will be given, but the result is unspecified.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.find_first_true(|&v| v * v > 50), Some(&8));
+    /// assert_eq!(x.find_first_true(|&v| v > 64), None);
+    /// ```
+    pub fn find_first_true<F>(&self, pred: F) -> Option<&T>
+    where
+        F: Fn(&T) -> bool,
+    {
+        // turn right while `pred` is false, the result is where we turned left last
+        self.node(last_left_turn(self.descend(|value| !pred(value))))
+    }
+
+    /// Returns `true` if the collection contains an element equal to `x`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// assert!(x.contains(4));
+    /// assert!(!x.contains(3));
+    /// ```
+    pub fn contains<X>(&self, x: X) -> bool
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        let i = self.descend(|value| x > value.borrow());
+        // tracking equality during the descent would cost a comparison per level, while checking the
+        // element found by `find_gte` only costs one
+        self.node(last_left_turn(i))
+            .map_or(false, |value| value.borrow() == x)
+    }
+
+    /// Returns `true` if the collection contains an element `v` such that `lo <= v <= hi`.
+    ///
+    /// This only looks up the smallest element `v >= lo`, and is cheaper than checking whether
+    /// [`range()`](Self::range) is empty. Returns `false` if `lo > hi`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// assert!(x.contains_range(3, 5));
+    /// assert!(x.contains_range(8, 10));
+    /// assert!(!x.contains_range(5, 7));
+    /// assert!(!x.contains_range(5, 3));
+    /// ```
+    pub fn contains_range<X>(&self, lo: X, hi: X) -> bool
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        // if `lo > hi`, any `v >= lo` is also `> hi`
+        self.find_gte(lo).map_or(false, |v| v.borrow() <= &hi)
+    }
+
+    /// Find the value `v` closest to `x` according to the distance function `dist`.
+    ///
+    /// Only the neighbors of `x` are considered, i.e. the largest `v < x` and the smallest `v >= x`, so
+    /// `dist` should grow with the distance between the elements in sorted order. If both neighbors are
+    /// equally far from `x`, the lower one is returned. Returns `None` if the collection is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16]);
+    /// let dist = |v: &i32, x: &i32| (v - x).abs();
+    /// assert_eq!(x.find_nearest_by(5, dist), Some(&4));
+    /// assert_eq!(x.find_nearest_by(7, dist), Some(&8));
+    /// assert_eq!(x.find_nearest_by(12, dist), Some(&8));
+    /// assert_eq!(x.find_nearest_by(100, dist), Some(&16));
+    /// ```
+    pub fn find_nearest_by<X, D, F>(&self, x: X, dist: F) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+        F: Fn(&T, &X) -> D,
+        D: Ord,
+    {
+        let x = x.borrow();
+        // the walk of `find_gte` also passes by the largest value less than `x`: it's where we last
+        // turned right
+        let i = self.descend(|value| x > value.borrow());
+        match (self.node(last_right_turn(i)), self.node(last_left_turn(i))) {
+            (Some(lo), Some(hi)) if dist(hi, x) < dist(lo, x) => Some(hi),
+            (Some(lo), _) => Some(lo),
+            (None, hi) => hi,
+        }
+    }
+
+    /// Find the value `v` closest to `x`, if it is at most `tol` away from it.
+    ///
+    /// The distance between `v` and `x` is the larger of them minus the smaller one, so this works
+    /// for unsigned numbers as well, as long as the distance fits in `X`. Like
+    /// [`find_nearest_by()`](Self::find_nearest_by), if both neighbors of `x` are equally far from
+    /// it, the lower one is returned. Returns `None` if the nearest value is further than `tol` away
+    /// from `x`, or if the collection is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![10u32, 20, 40]);
+    /// assert_eq!(x.find_within(23, 5), Some(&20));
+    /// assert_eq!(x.find_within(25, 5), Some(&20));
+    /// assert_eq!(x.find_within(30, 5), None);
+    /// ```
+    pub fn find_within<X>(&self, x: X, tol: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord + Copy + Sub<Output = X>,
+    {
+        let dist = |v: &T, x: &X| {
+            let (v, x) = (*v.borrow(), *x);
+            if v >= x {
+                v - x
+            } else {
+                x - v
+            }
+        };
+        self.find_nearest_by(x, dist)
+            .filter(|&v| dist(v, &x) <= tol)
+    }
+
+    /// Find where `x` falls relative to the elements of the collection.
+    ///
+    /// Returns [`Match::Exact`] if an element equal to `x` is found, or the neighbors of `x` otherwise.
+    /// This answers both [`find_gte()`](Self::find_gte) and [`find_lt()`](Self::find_lt) with a
+    /// single walk.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::{Match, OrderedCollection};
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// assert_eq!(x.locate(4), Match::Exact(&4));
+    /// assert_eq!(x.locate(5), Match::Between { lower: Some(&4), upper: Some(&8) });
+    /// assert_eq!(x.locate(0), Match::Between { lower: None, upper: Some(&1) });
+    /// assert_eq!(x.locate(9), Match::Between { lower: Some(&8), upper: None });
+    /// ```
+    pub fn locate<X>(&self, x: X) -> Match<'_, T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        if self.is_empty() {
+            return Match::Empty;
+        }
+        let i = self.descend(|value| x > value.borrow());
+        match (self.node(last_right_turn(i)), self.node(last_left_turn(i))) {
+            (_, Some(upper)) if upper.borrow() == x => Match::Exact(upper),
+            (lower, upper) => Match::Between { lower, upper },
+        }
+    }
+
+    /// Returns the number of values `v` such that `v < x`.
+    ///
+    /// Elements equal to `x` are not counted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 4, 8]);
+    /// assert_eq!(x.rank(0), 0);
+    /// assert_eq!(x.rank(2), 1);
+    /// assert_eq!(x.rank(4), 2);
+    /// assert_eq!(x.rank(5), 4);
+    /// assert_eq!(x.rank(9), 5);
+    /// ```
+    pub fn rank<X>(&self, x: X) -> usize
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        // rather than summing up the sizes of the subtrees we skip on the way down, we map the index
+        // of the smallest `v >= x` back to its position in sorted order (see `layout_rank()`)
+        self.partition_point(|value| x > value.borrow())
+    }
+
+    /// Find the smallest value `v` such that `v >= x`, along with its position in sorted order.
+    ///
+    /// This is equivalent to calling [`find_gte()`](Self::find_gte) and [`rank()`](Self::rank), but
+    /// only walks the tree once. Returns `None` if there is no such `v`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// assert_eq!(x.find_gte_with_rank(0), Some((0, &1)));
+    /// assert_eq!(x.find_gte_with_rank(3), Some((2, &4)));
+    /// assert_eq!(x.find_gte_with_rank(8), Some((3, &8)));
+    /// assert_eq!(x.find_gte_with_rank(9), None);
+    /// ```
+    pub fn find_gte_with_rank<X>(&self, x: X) -> Option<(usize, &T)>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        match self.find_gte_and_rank(x) {
+            (Some(value), rank) => Some((rank, value)),
+            (None, _) => None,
+        }
+    }
+
+    /// Find the smallest value `v` such that `v >= x`, along with the number of elements `< x`.
+    ///
+    /// Unlike [`find_gte_with_rank()`](Self::find_gte_with_rank), the count is returned even if
+    /// there is no such `v` (it is `len()` then), which makes it easy to count the elements between
+    /// boundaries by differencing ranks. Only walks the tree once.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 2, 4, 8]);
+    /// assert_eq!(x.find_gte_and_rank(2), (Some(&2), 1));
+    /// assert_eq!(x.find_gte_and_rank(9), (None, 5));
+    ///
+    /// // histogram of the elements in buckets [0, 2), [2, 5), [5, 10)
+    /// let boundaries = [0, 2, 5, 10];
+    /// let ranks: Vec<_> = boundaries.iter().map(|&b| x.find_gte_and_rank(b).1).collect();
+    /// let counts: Vec<_> = ranks.windows(2).map(|w| w[1] - w[0]).collect();
+    /// assert_eq!(counts, vec![1, 3, 1]);
+    /// ```
+    pub fn find_gte_and_rank<X>(&self, x: X) -> (Option<&T>, usize)
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        match last_left_turn(self.descend(|value| x > value.borrow())) {
+            0 => (None, self.len()),
+            i => (self.node(i), layout_rank(self.len(), i)),
+        }
+    }
+
+    /// Returns a cursor positioned right before the smallest value `v` such that `v >= x`.
+    ///
+    /// Calling [`next()`](Cursor::next) on the cursor yields `v` and the elements after it, while
+    /// [`prev()`](Cursor::prev) yields the elements before `v` in descending order. If there is no
+    /// such `v`, the cursor is positioned after the last element.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// let mut cursor = x.cursor_gte(3);
+    /// assert_eq!(cursor.next(), Some(&4));
+    /// assert_eq!(cursor.next(), Some(&8));
+    /// assert_eq!(cursor.next(), None);
+    ///
+    /// let mut cursor = x.cursor_gte(3);
+    /// assert_eq!(cursor.prev(), Some(&2));
+    /// assert_eq!(cursor.prev(), Some(&1));
+    /// assert_eq!(cursor.prev(), None);
+    /// ```
+    pub fn cursor_gte<X>(&self, x: X) -> Cursor<'_, T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        Cursor {
+            coll: self,
+            rank: self.partition_point(|value| x > value.borrow()),
+        }
+    }
+
+    /// Find the smallest value `v` such that `v >= x` for every `x` in `xs`.
+    ///
+    /// The result for `xs[k]` is written to `out[k]`. This is equivalent to calling
+    /// [`find_gte()`](Self::find_gte) for each query, but the descents of several queries are
+    /// interleaved so that the memory accesses of one query overlap with the comparisons of the
+    /// others.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `xs` and `out` have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// let mut out = [None; 4];
+    /// x.find_gte_batch(&[0, 3, 64, 65], &mut out);
+    /// assert_eq!(out, [Some(&1), Some(&4), Some(&64), None]);
+    /// ```
+    pub fn find_gte_batch<'a, X>(&'a self, xs: &[X], out: &mut [Option<&'a T>])
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        assert_eq!(
+            xs.len(),
+            out.len(),
+            "queries and output slices must have the same length"
+        );
+
+        // number of queries descending the tree together
+        const LANES: usize = 8;
+
+        let items = self.items();
+        let mask = prefetch_mask(items.len());
+
+        for (xs, out) in xs.chunks(LANES).zip(out.chunks_mut(LANES)) {
+            // Safety: the descent should not address items[0], because it is not initialized
+            let mut idx = [1; LANES];
+            let idx = &mut idx[..xs.len()];
+
+            // all leaves are at most one level apart, so the queries finish at about the same time
+            while idx.iter().any(|&i| i < items.len()) {
+                for (i, x) in idx.iter_mut().zip(xs) {
+                    if *i < items.len() {
+                        Prefetch::<T>::prefetch(items.as_ptr().cast(), *i, mask);
+
+                        // SAFETY: i < items.len(), so in-bounds
+                        // SAFETY: 1 <= i, so not [0], so initialized
+                        let value = unsafe { items.get_unchecked(*i).assume_init_ref() };
+                        *i = 2 * *i + usize::from(x > value.borrow());
+                    }
+                }
+            }
+
+            for (o, &i) in out.iter_mut().zip(idx.iter()) {
+                *o = self.node(last_left_turn(i));
+            }
+        }
+    }
+
+    /// Walks the tree from the root to a leaf, turning right whenever `go_right` holds for the
+    /// current node value, and returns the (out-of-bounds) index where the walk ended.
+    ///
+    /// The bits of the returned index below the leading 1 record the turns taken: a 0 bit is a left
+    /// turn and a 1 bit is a right turn. Use [`last_left_turn()`] or [`last_right_turn()`] to
+    /// decode it.
+    #[inline(always)]
+    fn descend<F>(&self, go_right: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        self.walk(Prefetch::<T>::prefetch, go_right)
+    }
+
+    /// Same as [`descend()`](Self::descend), but calls `prefetch` instead of [`Prefetch::prefetch()`]
+    /// for every node on the way.
+    #[inline(always)]
+    fn walk<P, F>(&self, prefetch: P, go_right: F) -> usize
+    where
+        P: Fn(*const T, usize, usize),
+        F: Fn(&T) -> bool,
+    {
+        eytzinger_descend(self.as_eytzinger_slice(), prefetch, go_right)
+    }
+}
+
+/// Methods that work with any fanout `B` (see [`OrderedCollection`]).
+#[cfg(feature = "alloc")]
+impl<T, const B: usize> OrderedCollection<T, B> {
+    /// Index of the first element in the storage.
+    ///
+    /// The binary layout is 1-based and leaves `[0]` unused. A layout with a fanout `B > 1` is
+    /// 0-based (see the `fanout` module), so its first node starts right at the cache line boundary.
+    const FIRST: usize = if B == 1 { 1 } else { 0 };
+
+    /// Allocates storage for the layout of `n` elements.
+    ///
+    /// The storage is aligned so that `[0]` starts at a cache line boundary. For the binary layout,
+    /// all the children we prefetch at once then share a single cache line (see
+    /// [`Prefetch::OFFSET`]). For a fanout `B > 1`, the keys start at `[0]` (see
+    /// [`FIRST`](Self::FIRST)), so nodes of a cache line in size don't straddle two of them.
+    ///
+    /// None of the returned elements are initialized.
+    ///
+    /// Panics if `n >= isize::MAX` (see [`OrderedCollection::from_sorted_iter()`]).
+    fn allocate(n: usize) -> Storage<T> {
+        // an allocation can't hold more than `isize::MAX` bytes anyway, so this only matters for
+        // zero-sized types
+        assert!(
+            n < isize::MAX as usize,
+            "OrderedCollection must hold fewer than isize::MAX elements"
+        );
+        Storage::new(n + Self::FIRST)
+    }
+
+    /// Returns the number of elements in the collection.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 3]);
+    /// assert_eq!(x.len(), 3);
+    /// ```
+    pub fn len(&self) -> usize {
+        // [0] of the binary layout is not an element of the collection
+        self.items().len() - Self::FIRST
+    }
+
+    /// Returns `true` if the collection contains no elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::<u32>::from(vec![]);
+    /// assert!(x.is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the elements in the order they are stored internally.
+    ///
+    /// This is the layout that [`from_eytzinger_vec()`](Self::from_eytzinger_vec) expects, so the
+    /// returned slice can be persisted and later turned back into an equivalent collection without
+    /// reordering it. `slice[i]` holds node `i + 1` of the Eytzinger layout, and there is no
+    /// sentinel, so the slice has exactly [`len()`](Self::len) elements. With a fanout `B > 1`, the
+    /// slice holds the keys of the nodes one after another, `B` keys per node.
+    ///
+    /// Note that the layout is an implementation detail, and may change across versions of this
+    /// crate. Only feed it back to the same version that produced it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from(vec![1, 2, 3, 4, 5, 6, 7]);
+    /// assert_eq!(a.as_eytzinger_slice(), [4, 2, 6, 1, 3, 5, 7]);
+    ///
+    /// let b = OrderedCollection::from_eytzinger_vec(a.as_eytzinger_slice().to_vec());
+    /// assert_eq!(b.find_gte(5), Some(&5));
+    /// ```
+    pub fn as_eytzinger_slice(&self) -> &[T] {
+        let items = &self.items()[Self::FIRST..];
+        // SAFETY: all elements from FIRST on are initialized, and `MaybeUninit<T>` has the same layout as `T`
+        unsafe { core::slice::from_raw_parts(items.as_ptr() as *const T, items.len()) }
+    }
+
+    /// Find the smallest value `v` such that `v >= x`.
+    ///
+    /// Returns `None` if there is no such `v`. If several elements are equal to `v`, the first of
+    /// them in sorted order is returned, the same one as `BTreeSet::range(x..)` would give. This
+    /// holds for all the lookups built on the same descent, like [`find_gte_ref()`](Self::find_gte_ref)
+    /// and [`find_gte_with_rank()`](Self::find_gte_with_rank).
+    ///
+    /// The query is taken by value and compared with the elements through [`Borrow`]. So a collection
+    /// of `String`s can be queried with a `String`, but not with a `&str`, as `String` only implements
+    /// `Borrow<str>` and not `Borrow<&str>`. Use [`find_gte_ref()`](Self::find_gte_ref) to query with
+    /// borrowed types.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.find_gte(0), Some(&1));
+    /// assert_eq!(x.find_gte(1), Some(&1));
+    /// assert_eq!(x.find_gte(3), Some(&4));
+    /// assert_eq!(x.find_gte(6), Some(&8));
+    /// assert_eq!(x.find_gte(8), Some(&8));
+    /// assert_eq!(x.find_gte(64), Some(&64));
+    /// assert_eq!(x.find_gte(65), None);
+    /// ```
+    pub fn find_gte<X>(&self, x: X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        self.find_gte_ref(&x)
+    }
+
+    /// Find the smallest value `v` such that `v >= *x`, taking the query by reference.
+    ///
+    /// Returns `None` if there is no such `v`. This is the same as [`find_gte()`](Self::find_gte),
+    /// but the query doesn't have to be moved or copied, and can be an unsized type that the elements
+    /// borrow as, like `str` for `String` elements.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// let q = 3;
+    /// assert_eq!(x.find_gte(q), Some(&4));
+    /// assert_eq!(x.find_gte_ref(&q), Some(&4));
+    ///
+    /// let x = OrderedCollection::from(vec!["apple".to_string(), "hello".to_string()]);
+    /// assert_eq!(x.find_gte_ref("banana").map(String::as_str), Some("hello"));
+    /// assert_eq!(x.find_gte_ref("zebra"), None);
+    /// ```
+    pub fn find_gte_ref<X>(&self, x: &X) -> Option<&T>
+    where
+        T: Borrow<X>,
+        X: Ord + ?Sized,
+    {
+        // queries above the largest element (or any query in an empty collection) can be answered
+        // without walking the tree
+        if self.node(self.max).map_or(true, |max| x > max.borrow()) {
+            return None;
+        }
+
+        let items = self.as_eytzinger_slice();
+        // `B` is known at compile time, so only one of the branches is left
+        let k = if B == 1 {
+            search_eytzinger(items, x)
+        } else {
+            fanout::search_fanout::<_, _, B>(items, x)
+        };
+        // SAFETY: both searches only return indices into the slice they were given
+        k.map(|k| unsafe { items.get_unchecked(k) })
+    }
+
+    /// Returns the value at the given index of the internal layout, or `None` if the index is 0.
+    ///
+    /// The index must be either 0 or a decoded result of [`descend()`](Self::descend). It is
+    /// 1-based for any fanout, i.e. `node(i)` is `as_eytzinger_slice()[i - 1]`, which for the
+    /// binary layout is just `[i]` of the storage.
+    #[inline(always)]
+    fn node(&self, i: usize) -> Option<&T> {
+        // SAFETY: i - 1 < len(), so in-bounds
+        // SAFETY: 1 <= i, so not [0] of the binary layout, so initialized
+        (i > 0).then(|| unsafe {
+            self.items()
+                .get_unchecked(i - 1 + Self::FIRST)
+                .assume_init_ref()
+        })
+    }
+
+    /// Returns the storage of the layout. For the binary layout, it starts with the uninitialized
+    /// `[0]` (see [`FIRST`](Self::FIRST)).
+    #[inline(always)]
+    fn items(&self) -> &[MaybeUninit<T>] {
+        &self.storage
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> OrderedCollection<T> {
+    /// Returns the number of elements the collection has room for.
+    ///
+    /// This is equal to [`len()`](Self::len), unless the collection was emptied with
+    /// [`clear()`](Self::clear), which keeps the allocation (see [`OrderedCollection`]), and not
+    /// shrunk with [`shrink_to_fit()`](Self::shrink_to_fit) since. The slot
+    /// for `[0]` is not included. Use [`memory_usage()`](Self::memory_usage) to get the size of the
+    /// allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut x = OrderedCollection::from(vec![1, 2, 3]);
+    /// assert_eq!(x.capacity(), 3);
+    /// x.extend(vec![4, 5]);
+    /// assert_eq!(x.capacity(), 5);
+    /// ```
+    pub fn capacity(&self) -> usize {
+        // [0] is not a slot for an element
+        self.storage.capacity() - 1
+    }
+
+    /// Returns the number of bytes occupied by the collection.
+    ///
+    /// This includes the collection itself and its allocation, i.e. room for
+    /// [`capacity()`](Self::capacity)` + 1` elements (see [`OrderedCollection`]). Memory owned by
+    /// the elements themselves, like the buffer of a `String`, is not included.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1u64; 1000]);
+    /// assert!(x.memory_usage() >= 1001 * 8);
+    /// ```
+    pub fn memory_usage(&self) -> usize {
+        mem::size_of::<Self>() + self.storage.capacity() * mem::size_of::<T>()
+    }
+
+    /// Returns the number of levels in the Eytzinger tree, i.e. `floor(log2(n)) + 1`.
+    ///
+    /// A lookup visits one node per level, so this is the number of comparisons each lookup makes.
+    /// An empty collection has a height of 0.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from((0..10).collect::<Vec<_>>());
+    /// assert_eq!(x.tree_height(), 4);
+    /// ```
+    pub fn tree_height(&self) -> u32 {
+        usize::BITS - self.len().leading_zeros()
+    }
+
+    /// Returns the number of nodes at each level of the Eytzinger tree, starting at the root.
+    ///
+    /// All levels but the last are full, i.e. level `k` holds `2^k` nodes. Together with the size of
+    /// `T`, this tells how many of the levels visited by a lookup fit in a given cache.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from((0..10).collect::<Vec<_>>());
+    /// assert_eq!(x.level_sizes(), vec![1, 2, 4, 3]);
+    /// ```
+    pub fn level_sizes(&self) -> Vec<usize> {
+        let n = self.len();
+        (0..self.tree_height())
+            .map(|k| {
+                let first = 1 << k;
+                // the last level holds nodes first..=n
+                (n + 1 - first).min(first)
+            })
+            .collect()
+    }
+
+    /// Returns the smallest element of the collection, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![8, 1, 4, 2]);
+    /// assert_eq!(x.first(), Some(&1));
+    /// ```
+    pub fn first(&self) -> Option<&T> {
+        self.node(self.min)
+    }
+
+    /// Returns the largest element of the collection, or `None` if it is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![8, 1, 4, 2]);
+    /// assert_eq!(x.last(), Some(&8));
+    /// ```
+    pub fn last(&self) -> Option<&T> {
+        self.node(self.max)
+    }
+
+    /// Returns the `k`-th smallest element of the collection (counting from 0), or `None` if
+    /// `k >= len()`.
+    ///
+    /// The position of the element in the layout is computed directly, so this takes O(1) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![8, 1, 4, 2]);
+    /// assert_eq!(x.select(0), Some(&1));
+    /// assert_eq!(x.select(2), Some(&4));
+    /// assert_eq!(x.select(3), Some(&8));
+    /// assert_eq!(x.select(4), None);
+    /// ```
+    pub fn select(&self, k: usize) -> Option<&T> {
+        if k < self.len() {
+            Some(self.by_rank(k))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the element at position `sorted_index` in ascending order, or `None` if it is out of
+    /// bounds.
+    ///
+    /// This is the same as [`select()`](Self::select), named after [`slice::get()`]. The collection
+    /// can also be indexed directly, which panics on out-of-bounds indices instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![8, 1, 4, 2]);
+    /// assert_eq!(x.get(2), Some(&4));
+    /// assert_eq!(x[2], 4);
+    /// assert_eq!(x.get(4), None);
+    /// ```
+    pub fn get(&self, sorted_index: usize) -> Option<&T> {
+        self.select(sorted_index)
+    }
+
+    /// Iterator over elements of a collection.
+    ///
+    /// It yields all items in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let coll = OrderedCollection::from(vec![8, 1, 4, 2]);
+    /// let values: Vec<_> = coll.iter().copied().collect();
+    /// assert_eq!(values, vec![1, 2, 4, 8]);
+    ///
+    /// let mut previous = 0;
+    /// for &value in &coll {
+    ///     assert!(previous <= value);
+    ///     previous = value;
+    /// }
+    /// ```
+    pub fn iter(&self) -> Iter<'_, T> {
+        Iter {
+            coll: self,
+            ranks: 0..self.len(),
+        }
+    }
+
+    /// Consumes the collection and returns its elements in ascending order.
+    ///
+    /// The elements are moved, not cloned. This is the same as `Vec::from(coll)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let coll = OrderedCollection::from(vec![8, 1, 4, 2]);
+    /// assert_eq!(coll.into_sorted_vec(), vec![1, 2, 4, 8]);
+    /// ```
+    pub fn into_sorted_vec(self) -> Vec<T> {
+        Vec::from(self)
+    }
+
+    /// Returns a clone of the elements in ascending order, leaving the collection intact.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let coll = OrderedCollection::from(vec![8, 1, 4, 2]);
+    /// assert_eq!(coll.to_sorted_vec(), vec![1, 2, 4, 8]);
+    /// assert_eq!(coll.find_gte(3), Some(&4));
+    /// ```
+    pub fn to_sorted_vec(&self) -> Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().cloned().collect()
+    }
+
+    /// Returns `true` if any value occurs in the collection more than once.
+    ///
+    /// Equal elements are adjacent in sorted order, so this compares every element with the next one,
+    /// stopping at the first duplicate. It takes O(n) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// assert!(OrderedCollection::from(vec![1, 2, 2, 3]).has_duplicates());
+    /// assert!(!OrderedCollection::from(vec![1, 2, 3]).has_duplicates());
+    /// ```
+    pub fn has_duplicates(&self) -> bool
+    where
+        T: PartialEq,
+    {
+        self.iter().zip(self.iter().skip(1)).any(|(a, b)| a == b)
+    }
+
+    /// Iterator over all elements `v` such that `lo <= v <= hi`.
+    ///
+    /// It yields the elements in ascending order. The iterator is empty if `lo > hi`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+    /// assert_eq!(x.range(2, 16).copied().collect::<Vec<_>>(), [2, 4, 8, 16]);
+    /// assert_eq!(x.range(3, 20).copied().collect::<Vec<_>>(), [4, 8, 16]);
+    /// assert_eq!(x.range(50, 100).copied().collect::<Vec<_>>(), [64]);
+    /// assert_eq!(x.range(65, 100).count(), 0);
+    /// assert_eq!(x.range(16, 2).count(), 0);
+    /// ```
+    pub fn range<X>(&self, lo: X, hi: X) -> Iter<'_, T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let (lo, hi) = (lo.borrow(), hi.borrow());
+        let start = self.partition_point(|value| lo > value.borrow());
+        let end = self.partition_point(|value| hi >= value.borrow());
+        Iter {
+            coll: self,
+            ranks: start..end.max(start),
+        }
+    }
+
+    /// Iterator over all elements `v` such that `v >= x`.
+    ///
+    /// It yields the elements in ascending order, like [`range()`](Self::range) with no upper
+    /// bound. The start is found with a single descent, and every step then takes O(1) time. The
+    /// iterator is empty if all elements are less than `x`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16]);
+    /// assert_eq!(x.iter_from(3).copied().collect::<Vec<_>>(), [4, 8, 16]);
+    /// assert_eq!(x.iter_from(16).copied().collect::<Vec<_>>(), [16]);
+    /// assert_eq!(x.iter_from(17).count(), 0);
+    /// ```
+    pub fn iter_from<X>(&self, x: X) -> Iter<'_, T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let x = x.borrow();
+        let start = self.partition_point(|value| x > value.borrow());
+        Iter {
+            coll: self,
+            ranks: start..self.len(),
+        }
+    }
+
+    /// Returns up to `k` of the smallest values `v` such that `v >= x`, in ascending order.
+    ///
+    /// This is [`iter_from(x)`](Self::iter_from) limited to `k` elements. If fewer than `k`
+    /// elements are `>= x`, all of them are returned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8, 16]);
+    /// assert_eq!(x.find_gte_n(3, 2), [&4, &8]);
+    /// assert_eq!(x.find_gte_n(5, 10), [&8, &16]);
+    /// ```
+    pub fn find_gte_n<X>(&self, x: X, k: usize) -> Vec<&T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        self.iter_from(x).take(k).collect()
+    }
+
+    /// Returns the number of elements `v` such that `lo <= v <= hi`.
+    ///
+    /// This is the length of [`range()`](Self::range), computed from the ranks of `lo` and `hi`
+    /// with two descents, without visiting the elements in between. Duplicates are all counted, and
+    /// the count is 0 if `lo > hi`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 4, 4, 8, 16]);
+    /// assert_eq!(x.count_range(2, 8), 5);
+    /// assert_eq!(x.count_range(4, 4), 3);
+    /// assert_eq!(x.count_range(5, 7), 0);
+    /// assert_eq!(x.count_range(8, 2), 0);
+    /// ```
+    pub fn count_range<X>(&self, lo: X, hi: X) -> usize
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let (lo, hi) = (lo.borrow(), hi.borrow());
+        let start = self.partition_point(|value| lo > value.borrow());
+        let end = self.partition_point(|value| hi >= value.borrow());
+        end.saturating_sub(start)
+    }
+
+    /// Returns the number of elements (in sorted order) for which `pred` holds before the first
+    /// element for which it doesn't, like [`slice::partition_point`] on the sorted elements.
+    ///
+    /// The elements must be partitioned by `pred`, i.e. `pred` must hold for a prefix of them in
+    /// sorted order, as it does for `|v| v < x`. Otherwise the result is unspecified. The tree is
+    /// descended once, and the position where the descent ends is mapped back to a position in
+    /// sorted order, so this takes O(log n) time. [`rank()`](Self::rank) is this with `|v| v < x`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 3, 3, 5, 6, 7]);
+    /// let i = x.partition_point(|&v| v < 5);
+    /// assert_eq!(i, 4);
+    /// assert_eq!(x[i], 5);
+    /// assert_eq!(x.partition_point(|_| true), 7);
+    /// assert_eq!(x.partition_point(|_| false), 0);
+    /// ```
+    pub fn partition_point<F>(&self, pred: F) -> usize
+    where
+        F: Fn(&T) -> bool,
+    {
+        // the first element `pred` doesn't hold for is the one where we turned left last
+        match last_left_turn(self.descend(pred)) {
+            0 => self.len(),
+            i => layout_rank(self.len(), i),
+        }
+    }
+
+    /// Inserts a single element into the collection.
+    ///
+    /// Duplicates are allowed, so inserting an element that is already present adds another copy
+    /// of it. The Eytzinger layout can't be updated in place, so this rebuilds the whole collection
+    /// in O(n) time. To add many elements, use [`Extend`] instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut a = OrderedCollection::from(vec![1, 2, 8]);
+    /// assert_eq!(a.find_gte(3), Some(&8));
+    /// a.insert(4);
+    /// assert_eq!(a.find_gte(3), Some(&4));
+    /// a.insert(4);
+    /// assert_eq!(a.len(), 5);
+    /// ```
+    pub fn insert(&mut self, value: T)
+    where
+        T: Ord,
+    {
+        self.rebuild(|v| {
+            let i = v.partition_point(|x| *x <= value);
+            v.insert(i, value);
+        });
+    }
+
+    /// Adds clones of the already sorted elements of `sorted` to the collection.
+    ///
+    /// This skips sorting the new elements, which [`Extend`] has to do: the new elements are merged
+    /// with the existing ones and the layout is rebuilt once, in O(n + m) time for `n` existing and
+    /// `m` new elements.
+    ///
+    /// If `sorted` is not sorted, the collection will give incorrect results. This is only checked in
+    /// debug builds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut a = OrderedCollection::from(vec![1, 4, 16]);
+    /// a.extend_from_sorted_slice(&[2, 8, 32]);
+    /// assert_eq!(a.find_gte(3), Some(&4));
+    /// assert_eq!(a.find_gte(20), Some(&32));
+    /// assert_eq!(a.len(), 6);
+    /// ```
+    pub fn extend_from_sorted_slice(&mut self, sorted: &[T])
+    where
+        T: Ord + Clone,
+    {
+        debug_assert!(
+            sorted.windows(2).all(|w| w[0] <= w[1]),
+            "the slice is not sorted"
+        );
+        self.rebuild(|v| {
+            v.extend_from_slice(sorted);
+            // a stable sort of two sorted runs boils down to merging them in linear time
+            v.sort();
+        });
+    }
+
+    /// Merges two collections into one that holds the elements of both.
+    ///
+    /// Both collections are already sorted, so they are merged in O(n + m) time for `n` and `m`
+    /// elements, and the layout is rebuilt once. Elements present in both collections are kept
+    /// twice, use [`dedup()`](Self::dedup) to turn the result into a set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from(vec![1, 4, 16]);
+    /// let b = OrderedCollection::from(vec![2, 4, 8]);
+    /// let c = a.merge(b);
+    /// assert_eq!(c.iter().copied().collect::<Vec<_>>(), vec![1, 2, 4, 4, 8, 16]);
+    /// ```
+    pub fn merge(mut self, other: Self) -> Self
+    where
+        T: Ord,
+    {
+        self.rebuild(|v| {
+            v.extend(Vec::from(other));
+            // a stable sort of two sorted runs boils down to merging them in linear time
+            v.sort();
+        });
+        self
+    }
+
+    /// Removes all elements from the collection.
+    ///
+    /// Like [`Vec::clear()`], this keeps the allocation: elements added afterwards, e.g. with
+    /// [`extend_from_sorted_slice()`](Self::extend_from_sorted_slice), are laid out in it as long
+    /// as they fit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut a = OrderedCollection::from(vec![1, 2, 3]);
+    /// a.clear();
+    /// assert!(a.is_empty());
+    /// assert_eq!(a.find_gte(0), None);
+    /// assert_eq!(a.capacity(), 3);
+    ///
+    /// a.extend_from_sorted_slice(&[4, 5]);
+    /// assert_eq!(a.find_gte(0), Some(&4));
+    /// assert_eq!(a.capacity(), 3);
+    /// ```
+    pub fn clear(&mut self) {
+        let items = &mut self.storage[1..] as *mut [MaybeUninit<T>] as *mut [T];
+        // empty the collection first, so that the elements can't be dropped twice if one of them
+        // panics
+        self.storage.set_len(1);
+        self.min = 0;
+        self.max = 0;
+        // SAFETY: all elements beyond [0] were initialized, and are no longer part of the collection
+        unsafe { ptr::drop_in_place(items) };
+    }
+
+    /// Shrinks the allocation of the collection to fit its elements.
+    ///
+    /// Only [`clear()`](Self::clear) leaves spare capacity, so this does nothing unless the
+    /// collection was cleared since it was last rebuilt into a new allocation. The elements keep
+    /// their places in the layout, they are only moved to a smaller allocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut a = OrderedCollection::from(vec![1, 2, 3, 4, 5]);
+    /// a.clear();
+    /// a.extend_from_sorted_slice(&[6, 7]);
+    /// assert_eq!(a.capacity(), 5);
+    /// a.shrink_to_fit();
+    /// assert_eq!(a.capacity(), 2);
+    /// assert_eq!(a.find_gte(0), Some(&6));
+    /// ```
+    pub fn shrink_to_fit(&mut self) {
+        let n = self.len();
+        if self.capacity() == n {
+            return;
+        }
+        let mut storage = Self::allocate(n);
+        // SAFETY: both buffers hold at least `n + 1` elements. The elements are moved to the same
+        // indices of the new storage, and the old one never drops them.
+        unsafe { ptr::copy_nonoverlapping(self.items().as_ptr(), storage.as_mut_ptr(), n + 1) };
+        self.storage = storage;
+    }
+
+    /// Retains only the elements for which `f` returns `true`.
+    ///
+    /// The elements are visited in ascending order. Removing elements keeps the remaining ones sorted,
+    /// but the Eytzinger layout still has to be rebuilt, which takes O(n) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut a = OrderedCollection::from(vec![1, 2, 3, 4, 5, 6]);
+    /// a.retain(|&v| v % 2 == 0);
+    /// assert_eq!(a.find_gte(3), Some(&4));
+    /// assert_eq!(a.len(), 3);
+    /// ```
+    pub fn retain<F>(&mut self, f: F)
+    where
+        F: FnMut(&T) -> bool,
+    {
+        self.rebuild(|v| v.retain(f));
+    }
+
+    /// Splits the collection at `x`, returning the elements `v >= x` in a new collection.
+    ///
+    /// The elements `v < x` are left in `self`. The split point is found by descending the tree as
+    /// in [`rank()`](Self::rank), but the layouts of both halves have to be rebuilt, which takes O(n)
+    /// time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut a = OrderedCollection::from(vec![1, 2, 3, 4, 5, 6]);
+    /// let b = a.split_off(4);
+    /// assert_eq!(a.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// assert_eq!(b.iter().copied().collect::<Vec<_>>(), vec![4, 5, 6]);
+    /// ```
+    pub fn split_off<X>(&mut self, x: X) -> OrderedCollection<T>
+    where
+        T: Borrow<X>,
+        X: Ord,
+    {
+        let at = self.rank(x);
+        let mut tail = Vec::new();
+        self.rebuild(|v| tail = v.split_off(at));
+        OrderedCollection::from_sorted_iter(tail)
+    }
+
+    /// Removes all but one of each group of equal elements, turning the collection into a set.
+    ///
+    /// Equal elements are adjacent in sorted order, so this works like [`Vec::dedup()`]. The Eytzinger
+    /// layout is rebuilt afterwards, which takes O(n) time.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let mut a = OrderedCollection::from(vec![1, 3, 1, 2, 3, 3]);
+    /// a.dedup();
+    /// assert_eq!(a.len(), 3);
+    /// assert_eq!(a.iter().copied().collect::<Vec<_>>(), vec![1, 2, 3]);
+    /// ```
+    pub fn dedup(&mut self)
+    where
+        T: PartialEq,
+    {
+        self.rebuild(|v| v.dedup());
+    }
+
+    /// Rebuilds the collection after `f` modifies its elements, which are given in sorted order.
+    ///
+    /// `f` must leave the elements sorted. If `f` panics, the collection is left empty.
+    ///
+    /// The layout is rebuilt in the same allocation if the collection has spare capacity (which only
+    /// [`clear()`](Self::clear) leaves) and the elements fit in it. Otherwise, it is moved to a new
+    /// allocation of the exact size.
+    fn rebuild<F>(&mut self, f: F)
+    where
+        F: FnOnce(&mut Vec<T>),
+    {
+        let spare = self.capacity() > self.len();
+        let mut v = self.take_sorted();
+        f(&mut v);
+        let n = v.len();
+        let mut storage = if spare && n <= self.capacity() {
+            let mut storage = mem::take(&mut self.storage);
+            storage.set_len(n + 1);
+            storage
+        } else {
+            Self::allocate(n)
+        };
+        // nothing can panic between taking the storage and putting it back, as filling it from a
+        // `Vec` doesn't run any code of `T`
+        eytzinger_fill(&mut storage, v.into_iter());
+        *self = Self::from_storage(storage);
+    }
+
+    /// Moves all the elements out in sorted order, leaving the collection empty.
+    ///
+    /// The allocation is kept, so the collection has spare capacity afterwards.
+    fn take_sorted(&mut self) -> Vec<T> {
+        let n = self.len();
+        // allocate first, so nothing can panic while the elements are being moved out
+        let mut v = Vec::with_capacity(n);
+        for rank in 0..n {
+            // SAFETY: every rank maps to a distinct index in 1..=n, so each initialized element is
+            // moved out exactly once. The collection is emptied below, so they are not dropped again.
+            v.push(unsafe {
+                self.items()
+                    .get_unchecked(layout_index(n, rank))
+                    .assume_init_read()
+            });
+        }
+        self.storage.set_len(1);
+        self.min = 0;
+        self.max = 0;
+        v
+    }
+
+    /// Returns a reference to the element at position `rank` in sorted order.
+    ///
+    /// Requires `rank < self.len()`.
+    fn by_rank(&self, rank: usize) -> &T {
+        let i = layout_index(self.len(), rank);
+        // SAFETY: layout_index() maps ranks below len() into 1..=len(), so in-bounds and initialized
+        unsafe { self.items().get_unchecked(i).assume_init_ref() }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<T: serde::Serialize> serde::Serialize for OrderedCollection<T> {
+    /// Serializes the collection as a sequence of its elements in ascending order.
+    ///
+    /// The internal Eytzinger layout is not part of the serialized representation.
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq((0..self.len()).map(|rank| self.by_rank(rank)))
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T: Ord + serde::Deserialize<'de>> serde::Deserialize<'de> for OrderedCollection<T> {
+    /// Deserializes a collection from a sequence of elements in ascending order.
+    ///
+    /// Returns an error if the elements are not sorted.
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let v = Vec::<T>::deserialize(deserializer)?;
+        if v.windows(2).any(|w| w[0] > w[1]) {
+            return Err(serde::de::Error::custom("elements are not sorted"));
+        }
+        Ok(Self::from_sorted_iter(v))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> IntoIterator for &'a OrderedCollection<T> {
+    type Item = &'a T;
+    type IntoIter = Iter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> IntoIterator for OrderedCollection<T> {
+    type Item = T;
+    type IntoIter = alloc::vec::IntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        Vec::from(self).into_iter()
+    }
+}
+
+/// Immutable iterator over elements in a [`OrderedCollection`]
+///
+/// Created by [`OrderedCollection::iter()`].
+#[cfg(feature = "alloc")]
+pub struct Iter<'a, T> {
+    coll: &'a OrderedCollection<T>,
+    /// Sorted positions of the elements that are yet to be yielded
+    ranks: Range<usize>,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> Iterator for Iter<'a, T> {
+    type Item = &'a T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        // ranks are always below coll.len(), as required by by_rank()
+        self.ranks.next().map(|rank| self.coll.by_rank(rank))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.ranks.size_hint()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> DoubleEndedIterator for Iter<'a, T> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.ranks.next_back().map(|rank| self.coll.by_rank(rank))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> ExactSizeIterator for Iter<'a, T> {}
+
+/// Error returned by [`OrderedCollection::try_from_sorted_iter()`] if the elements are not sorted
+///
+/// It implements `std::error::Error` with the (non-default) `std` feature.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct UnsortedError {
+    index: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl UnsortedError {
+    /// Position of the first element that is smaller than the element before it
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for UnsortedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "element at index {} is smaller than its predecessor",
+            self.index
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for UnsortedError {}
+
+/// Error returned by [`OrderedCollection::try_from_partial_ord()`] if an element can't be compared
+///
+/// It implements `std::error::Error` with the (non-default) `std` feature.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct IncomparableError {
+    index: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl IncomparableError {
+    /// Position of the first element that can't be compared with itself, like a NaN
+    pub fn index(&self) -> usize {
+        self.index
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for IncomparableError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "element at index {} can't be compared with other elements",
+            self.index
+        )
+    }
+}
+
+#[cfg(feature = "std")]
+impl std::error::Error for IncomparableError {}
+
+/// Cursor over the elements of an [`OrderedCollection`] in sorted order
+///
+/// The cursor points between two adjacent elements. It is created by
+/// [`OrderedCollection::cursor_gte()`], and can be moved in both directions, taking O(1) time per
+/// step.
+#[cfg(feature = "alloc")]
+pub struct Cursor<'a, T> {
+    coll: &'a OrderedCollection<T>,
+    /// Sorted position of the element after the cursor, at most `coll.len()`
+    rank: usize,
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> Cursor<'a, T> {
+    /// Moves the cursor one element back and returns the element it passed over.
+    ///
+    /// Returns `None` if the cursor is already before the first element.
+    pub fn prev(&mut self) -> Option<&'a T> {
+        if self.rank == 0 {
+            return None;
+        }
+        self.rank -= 1;
+        Some(self.coll.by_rank(self.rank))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<'a, T> Iterator for Cursor<'a, T> {
+    type Item = &'a T;
+
+    /// Moves the cursor one element forward and returns the element it passed over.
+    ///
+    /// Returns `None` if the cursor is already after the last element.
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.rank == self.coll.len() {
+            return None;
+        }
+        self.rank += 1;
+        Some(self.coll.by_rank(self.rank - 1))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = self.coll.len() - self.rank;
+        (remaining, Some(remaining))
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T> From<OrderedCollection<T>> for Vec<T> {
+    /// Converts all elements into a new [`Vec`] in ascending order
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let coll = OrderedCollection::from(vec![5, 3, 1, 4, 2]);
+    /// let values = Vec::from(coll);
+    /// assert_eq!(values, vec![1, 2, 3, 4, 5]);
+    /// ```
+    fn from(mut value: OrderedCollection<T>) -> Self {
+        value.take_sorted()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Clone> Clone for OrderedCollection<T> {
+    /// Clones the collection, preserving its layout so that no sorting or reordering is needed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+    /// let y = x.clone();
+    /// assert_eq!(y.find_gte(3), Some(&4));
+    /// ```
+    fn clone(&self) -> Self {
+        let mut storage = Self::allocate(self.len());
+        // [0] is left uninitialized as usual
+        for (slot, value) in storage.iter_mut().zip(self.items()).skip(1) {
+            // SAFETY: all elements beyond [0] are initialized
+            *slot = MaybeUninit::new(unsafe { value.assume_init_ref() }.clone());
+        }
+        Self::from_storage(storage)
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: PartialEq> PartialEq for OrderedCollection<T> {
+    /// Two collections are equal if they hold equal elements in sorted order.
+    ///
+    /// The layout only depends on the number of elements, which puts the element of any given rank at
+    /// the same index. So comparing the layouts element by element is the same as comparing the
+    /// elements in sorted order, regardless of how the collections were constructed.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let a = OrderedCollection::from(vec![8, 1, 4, 2]);
+    /// let b = OrderedCollection::from_sorted_iter(vec![1, 2, 4, 8]);
+    /// assert_eq!(a, b);
+    /// assert_ne!(a, OrderedCollection::from(vec![1, 2, 4]));
+    /// ```
+    fn eq(&self, other: &Self) -> bool {
+        self.as_eytzinger_slice() == other.as_eytzinger_slice()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: Eq> Eq for OrderedCollection<T> {}
+
+#[cfg(feature = "alloc")]
+impl<T> Index<usize> for OrderedCollection<T> {
+    type Output = T;
+
+    /// Returns the element at position `sorted_index` in ascending order.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sorted_index >= len()`, like indexing a slice. Use
+    /// [`get()`](OrderedCollection::get) to handle that case instead.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![8, 1, 4, 2]);
+    /// assert_eq!(x[0], 1);
+    /// assert_eq!(x[3], 8);
+    /// ```
+    fn index(&self, sorted_index: usize) -> &T {
+        match self.get(sorted_index) {
+            Some(value) => value,
+            None => panic!(
+                "index out of bounds: the len is {} but the index is {}",
+                self.len(),
+                sorted_index
+            ),
+        }
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T: fmt::Debug> fmt::Debug for OrderedCollection<T> {
+    /// Formats the elements of the collection in ascending order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![8, 1, 4, 2]);
+    /// assert_eq!(format!("{:?}", x), "OrderedCollection [1, 2, 4, 8]");
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("OrderedCollection ")?;
+        f.debug_list().entries(self.iter()).finish()
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl<T, const B: usize> Drop for OrderedCollection<T, B> {
+    fn drop(&mut self) {
+        // the storage is empty if it was taken by `rebuild()`
+        if let Some(items) = self.storage.get_mut(Self::FIRST..) {
+            // SAFETY: all elements from FIRST on are initialized, so can be dropped.
+            // `[0]` is left as is. the storage then only frees the allocation, which is fine since it
+            // holds `MaybeUninit<T>` and never calls `T::drop`.
+            unsafe { ptr::drop_in_place(items as *mut [MaybeUninit<T>] as *mut [T]) };
+        }
+    }
+}
+
+/// Prefetching for the Eytzinger layout of `T`s, shared by [`OrderedCollection`] and
+/// [`OrderedArray`].
+struct Prefetch<T>(PhantomData<T>);
+
+impl<T> Prefetch<T> {
+    /// this computation is a little finicky, so let's walk through it.
+    ///
+    /// we want to prefetch a couple of levels down in the tree from where we are.
+    /// however, we can only fetch one cacheline at a time (assume a line holds 64b).
+    /// we therefore need to find at what depth a single prefetch fetches all the descendants.
+    /// it turns out that, at depth k under some node with index i, the leftmost child is at:
+    ///
+    ///   2^k * i
+    ///
+    /// this follows from the fact that the leftmost immediate child of node i is at 2i by
+    /// recursively expanding i. Note that the original paper uses 0-based indexing (`2i + 1`/`2i + 2`) while we
+    /// use 1-based indexing (`2i`/`2i + 1`). This is because of performance reasons (see:
+    /// [Optimized Eytzinger layout & memory prefetch](https://github.com/jonhoo/ordsearch/pull/27)).
+    ///
+    /// If you're curious, the rightmost child is at:
+    ///
+    ///   2^k * i + 2^k - 1
+    ///
+    /// at depth k, there are 2^k children. we can fit 64/sizeof(T) children in a cacheline, so
+    /// we want to use the depth k that has 64/sizeof(T) children. so, we want:
+    ///
+    ///   2^k = 64/sizeof(T)
+    ///
+    /// but, we don't actually *need* k. we only ever use 2^k. so, we can just use 64/sizeof(T)
+    /// directly! nice. we call this the multiplier (because it's what we'll multiply i by).
+    ///
+    /// zero-sized types don't occupy any cache lines, so there is nothing to prefetch. we just need
+    /// to avoid dividing by zero.
+    const MULTIPLIER: usize = match mem::size_of::<T>() {
+        0 => 1,
+        size => 64 / size,
+    };
+
+    /// now we know that multiplier == 2^k, so we're done. right?
+    ///
+    /// right?
+    ///
+    /// well, only sort of. the prefetch instruction fetches the cache-line that *holds* the
+    /// given memory address. let's denote cache lines with []. what if we have:
+    ///
+    ///   [..., 2^k + 2^k-1] [2^k + 2^k, ...]
+    ///
+    /// essentially, we got unlucky with the alignment so that the leftmost child is not sharing
+    /// a cacheline with any of the other items at that level! that's not great. so, instead, we
+    /// prefetch the address that is half-way through the set of children. that way, we ensure
+    /// that we prefetch at least half of the items.
+    ///
+    /// to avoid this problem altogether, we also align the storage so that `[0]` is at the start of a
+    /// cache line (see `allocate()`). then, the leftmost child at depth k is always at the start of a
+    /// cache line and all 2^k children share it. the offset doesn't hurt in that case, as it stays
+    /// within the same line.
+    const OFFSET: usize = Self::MULTIPLIER / 2;
+
+    /// all of this works great when a cache line holds many elements. for large `T` though, the
+    /// multiplier degrades to 1 (or even 0), and a single prefetch only reaches one level down (or
+    /// none), which is not enough to hide memory latency.
+    ///
+    /// in that case, we do "deep" prefetching instead: we prefetch all the descendants this many
+    /// levels down, issuing one prefetch per descendant. they sit next to each other in memory, so
+    /// they span consecutive cache lines.
+    const DEEP_LEVELS: u32 = 2;
+
+    /// Prefetches the descendants of node `i` that the search will need a few levels down.
+    ///
+    /// `base` is the address of `[0]`, and `mask` must be the [`prefetch_mask()`] for the length of
+    /// the layout (including `[0]`).
+    #[inline(always)]
+    fn prefetch(base: *const T, i: usize, mask: usize) {
+        // the offsets below may overflow for large collections of small elements. Wrapping is fine here,
+        // because the offset only changes which address is prefetched, never which element is read.
+        if Self::MULTIPLIER >= 1 << Self::DEEP_LEVELS {
+            // the search loop is arithmetic-bound, not memory-bound when using prefetch. So offset part
+            // of prefetch address is intentionally not masked, it allows to do less arithmetic in the loop.
+            // It doesn't affect masking much because `Self::OFFSET` is just half of a cache line.
+            // (see: [Optimized Eytzinger layout & memory prefetch](https://github.com/jonhoo/ordsearch/pull/27))
+            let offset = Self::MULTIPLIER.wrapping_mul(i) & mask;
+            do_prefetch(base.wrapping_add(Self::OFFSET + offset));
+        } else {
+            let first = i.wrapping_shl(Self::DEEP_LEVELS);
+            for j in 0..1 << Self::DEEP_LEVELS {
+                let offset = first.wrapping_add(j) & mask;
+                do_prefetch(base.wrapping_add(offset));
+            }
+        }
+    }
+
+    /// Prefetches all the descendants of node `i` that are `LEVELS` levels down, issuing one prefetch
+    /// per cache line they span.
+    ///
+    /// See [`Self::prefetch()`] for the arguments.
+    #[cfg(feature = "alloc")]
+    #[inline(always)]
+    fn prefetch_levels<const LEVELS: u32>(base: *const T, i: usize, mask: usize) {
+        // the descendants of `i` at depth `LEVELS` are at `i * 2^LEVELS..(i + 1) * 2^LEVELS`
+        let first = i.wrapping_shl(LEVELS);
+        // elements larger than a cache line have a multiplier of 0, and get one prefetch each
+        for j in (0..1 << LEVELS).step_by(Self::MULTIPLIER.max(1)) {
+            let offset = first.wrapping_add(j) & mask;
+            do_prefetch(base.wrapping_add(offset));
+        }
+    }
+}
+
+/// Hints the CPU to load the cache line at `addr`.
+///
+/// The addresses computed by [`Prefetch::prefetch()`] may point past the end of the
+/// allocation: the offset is masked with [`prefetch_mask()`], which only rounds the length up to a
+/// power of two, and [`Prefetch::OFFSET`] is added on top. This is sound:
+///  - the addresses are computed with `wrapping_add`, which is defined for any offset (unlike
+///    `add`) as long as the pointer is not dereferenced;
+///  - `addr` is never dereferenced. The intrinsics have no effect on the behavior of the program,
+///    and Miri treats them as no-ops;
+///  - the prefetch instructions it lowers to (`prefetcht0` on x86, `prfm` on ARM) don't fault on
+///    invalid or unmapped addresses, they are just dropped.
+///
+/// Out of bounds prefetches only waste a bit of memory bandwidth, which is why the mask keeps most
+/// of them within the layout.
+///
+/// With the `nightly` feature, the generic prefetch intrinsic is used on every target. Otherwise,
+/// prefetching is only available through `_mm_prefetch` on x86 and x86_64 (with SSE, which is
+/// always present on x86_64) and through the `prfm` instruction on AArch64, and this is a no-op
+/// elsewhere.
+#[cfg(feature = "nightly")]
+#[inline(always)]
+fn do_prefetch<T>(addr: *const T) {
+    core::intrinsics::prefetch_read_data::<T, 3>(addr);
+}
+
+#[cfg(all(
+    not(feature = "nightly"),
+    any(target_arch = "x86", target_arch = "x86_64"),
+    target_feature = "sse"
+))]
+#[inline(always)]
+fn do_prefetch<T>(addr: *const T) {
+    #[cfg(target_arch = "x86")]
+    use core::arch::x86::{_mm_prefetch, _MM_HINT_T0};
+    #[cfg(target_arch = "x86_64")]
+    use core::arch::x86_64::{_mm_prefetch, _MM_HINT_T0};
+
+    // SAFETY: `_mm_prefetch` never dereferences `addr` (see above), and SSE is enabled
+    unsafe { _mm_prefetch::<_MM_HINT_T0>(addr.cast()) };
+}
+
+// Miri can't run inline assembly
+#[cfg(all(not(feature = "nightly"), target_arch = "aarch64", not(miri)))]
+#[inline(always)]
+fn do_prefetch<T>(addr: *const T) {
+    // SAFETY: `prfm` never faults and doesn't access memory observably (see above)
+    unsafe {
+        core::arch::asm!(
+            "prfm pldl1keep, [{addr}]",
+            addr = in(reg) addr,
+            options(nostack, readonly, preserves_flags)
+        );
+    }
+}
+
+#[cfg(not(any(
+    feature = "nightly",
+    all(
+        any(target_arch = "x86", target_arch = "x86_64"),
+        target_feature = "sse"
+    ),
+    all(target_arch = "aarch64", not(miri))
+)))]
+fn do_prefetch<T>(_addr: *const T) {}
+
+/// Walks the tree in Eytzinger layout from the root to a leaf, turning right whenever `go_right` holds
+/// for the current node value, and returns the (out-of-bounds) index where the walk ended.
+///
+/// `nodes[k]` holds node `k + 1` of the (1-based) layout. `prefetch` is called for every node on the
+/// way, with the address of (the non-existent) node 0, the index of the node and the
+/// [`prefetch_mask()`] for the layout.
+#[inline(always)]
+fn eytzinger_descend<T, P, F>(nodes: &[T], prefetch: P, go_right: F) -> usize
+where
+    P: Fn(*const T, usize, usize),
+    F: Fn(&T) -> bool,
+{
+    // the length of the 1-based layout. `nodes.len() < isize::MAX`, so this can't overflow
+    let len = nodes.len() + 1;
+    // only used for computing prefetch addresses, never dereferenced
+    let base = nodes.as_ptr().wrapping_sub(1);
+    let mask = prefetch_mask(len);
+    let mut i = 1;
+
+    // `2 * i + 1` can't overflow: `i < len <= isize::MAX`
+    while i < len {
+        prefetch(base, i, mask);
+
+        // SAFETY: 1 <= i < len, so in-bounds
+        let value = unsafe { nodes.get_unchecked(i - 1) };
+        // using branchless index update. At the moment compiler cannot reliably tranform
+        // if expressions to branchless instructions like `cmov` and `setb`
+        i = 2 * i + usize::from(go_right(value));
+    }
+
+    i
+}
+
+/// Find the position of the smallest value `v` such that `v >= x` in a slice in Eytzinger layout.
+///
+/// `items` must be in the layout returned by [`OrderedCollection::as_eytzinger_slice()`], and the
+/// returned index points into it. This allows to search a layout that is borrowed from elsewhere
+/// (e.g. memory-mapped or built by [`OrderedCollection::from_eytzinger_vec()`] and taken apart
+/// again) without taking ownership of it. Returns `None` if there is no such `v`.
+///
+/// If `items` is not in Eytzinger layout, no error will be given, but the result is unspecified.
+///
+/// # Examples
+///
+/// ```
+/// # use ordsearch::search_eytzinger;
+/// // the layout of [1, 2, 4, 8, 16, 32, 64]
+/// let items = [8, 2, 32, 1, 4, 16, 64];
+/// assert_eq!(search_eytzinger(&items, &3), Some(4));
+/// assert_eq!(search_eytzinger(&items, &64), Some(6));
+/// assert_eq!(search_eytzinger(&items, &65), None);
+/// ```
+pub fn search_eytzinger<T, X>(items: &[T], x: &X) -> Option<usize>
+where
+    T: Borrow<X>,
+    X: Ord + ?Sized,
+{
+    let i = eytzinger_descend(items, Prefetch::<T>::prefetch, |value| x > value.borrow());
+
+    // Because the branchless loop navigates the tree until we reach a leaf node regardless of whether
+    // the value is found or not, we now need to decode the found value index, if any.
+    //
+    // To understand how this works, it is useful to think of the index as a binary number.
+    // The index update strategy always multiplies the index by 2 (which can be seen as `i <<= 1`)
+    // and then adds 1 (which can be seen as `i |= 1`) if the value is greater than the current node value.
+    // So, we can interpret the bits in index as a history of turns we made in the tree: a 0 bit means
+    // we went left, a 1 bit means we went right.
+    //
+    // Another important observation is that when we find the target value, we make a left turn
+    // and the corresponding bit in the index will be 0. More importantly, all subsequent bits
+    // will be 1, because after we made a left turn we ended up in a subtree where all values
+    // are less than the target value.
+    //
+    // Therefore, to decode the index we need to:
+    //   1. get rid of all trailing 1 bits (dummy turns we made after we found the target value)
+    //   2. get rid of one more bit to restore the index state before we made a left turn at the target element
+    //   3. check if the resulting index is greater than 0 (0 means the target value is not in the tree)
+    //
+    // `items` doesn't include [0], so the index is shifted by one.
+    last_left_turn(i).checked_sub(1)
+}
+
+/// Maps the position `rank` in sorted order to an index in the Eytzinger layout of `n` elements.
+///
+/// If the tree was perfect (all levels full), then node `i` at depth `d` with height `h` would have rank
+/// `(2 * (i - 2^d) + 1) * 2^(h - d) - 1`, which can be inverted by counting trailing zeros of
+/// `rank + 1`. The last level of the tree is only partially filled though. Its nodes hold every other
+/// rank from the left, so missing leaf nodes shift all ranks that come after them. We account for
+/// that by mapping `rank` to the rank it would have had in the perfect tree first.
+///
+/// Requires `rank < n`.
+#[cfg(feature = "alloc")]
+fn layout_index(n: usize, rank: usize) -> usize {
+    debug_assert!(rank < n);
+    // depth of the last level
+    let height = usize::BITS - 1 - n.leading_zeros();
+    // number of nodes on the last level
+    let leaves = n - (1 << height) + 1;
+    let perfect_rank = if rank < 2 * leaves {
+        rank
+    } else {
+        2 * (rank - leaves) + 1
+    };
+    let r = perfect_rank + 1;
+    let zeros = r.trailing_zeros();
+    (1 << (height - zeros)) | (r >> (zeros + 1))
+}
+
+/// Maps the index `i` in the Eytzinger layout of `n` elements to its position in sorted order.
+///
+/// This is the inverse of [`layout_index()`]: we compute the rank node `i` would have in the
+/// perfect tree, and then account for the leaf nodes missing before it.
+///
+/// Requires `0 < i <= n`.
+const fn layout_rank(n: usize, i: usize) -> usize {
+    debug_assert!(0 < i && i <= n);
+    let height = usize::BITS - 1 - n.leading_zeros();
+    let leaves = n - (1 << height) + 1;
+    let depth = usize::BITS - 1 - i.leading_zeros();
+    let perfect_rank = ((2 * (i - (1 << depth)) + 1) << (height - depth)) - 1;
+    if perfect_rank < 2 * leaves {
+        perfect_rank
+    } else {
+        (perfect_rank - 1) / 2 + leaves
+    }
+}
+
+/// Decodes the index of the node where the walk encoded in `i` made its last left turn.
+///
+/// Returns 0 if the walk never turned left.
+#[inline(always)]
+fn last_left_turn(i: usize) -> usize {
+    i >> (i.trailing_ones() + 1)
+}
+
+/// Decodes the index of the node where the walk encoded in `i` made its last right turn.
+///
+/// Returns 0 if the walk never turned right.
+#[inline(always)]
+fn last_right_turn(i: usize) -> usize {
+    i >> (i.trailing_zeros() + 1)
+}
+
+/// Calculates the prefetch mask for a given collection size.
+///
+/// Creates a binary mask that fully covers a given [`usize`] value (e.g., for the value `0b100`, the mask is `0b111`).
+/// The prefetch mask is used to keep an element address inside the array boundaries when prefetching next values from
+/// memory.
+///
+/// It is totally valid to prefetch invalid addresses from an x86 perspective[^1], but such prefetches do not
+/// aid algorithm performance and may worsen it by thrashing the CPU cache. Instead of prefetching outside
+/// the array boundaries, we use a prefetch mask to zero the offset and prefetch the first elements of the array
+/// instead, aiding subsequent searches. Essentially, masking the offset is a cheaper alternative to the
+/// `offset % size` function.
+///
+/// [^1]: [Intel® 64 and IA-32 Architectures Software Developer’s Manual](https://software.intel.com/en-us/download/intel-64-and-ia-32-architectures-sdm-combined-volumes-1-2a-2b-2c-2d-3a-3b-3c-3d-and-4)
+fn prefetch_mask(n: usize) -> usize {
+    if n > 0 {
+        usize::MAX >> n.leading_zeros()
+    } else {
+        0
     }
 }
 
-#[cfg(feature = "nightly")]
-#[inline(always)]
-fn do_prefetch<T>(addr: *const T) {
-    unsafe {
-        core::intrinsics::prefetch_read_data(addr, 3);
+#[cfg(all(test, feature = "alloc"))]
+mod tests {
+    use super::*;
+    use alloc::{boxed::Box, vec};
+
+    /// Pseudorandom values in `0..1024`, generated with a LCG for reproducibility.
+    fn pseudorandom(mut seed: u64) -> impl Iterator<Item = u32> {
+        core::iter::from_fn(move || {
+            // LCG constants from https://en.wikipedia.org/wiki/Numerical_Recipes.
+            seed = seed.wrapping_mul(1664525).wrapping_add(1013904223);
+            Some((seed >> 32) as u32 % 1024)
+        })
+    }
+
+    proptest::proptest! {
+        /// Compares lookups with a `BTreeSet` on random collections. Values are drawn from a small
+        /// domain, so most collections have duplicates, and queries fall below, between and above them.
+        #[test]
+        // far too slow under Miri, and the unsafe code is covered by the other tests
+        #[cfg_attr(miri, ignore)]
+        fn matches_btreeset(
+            v in proptest::collection::vec(0u32..64, 0..300),
+            queries in proptest::collection::vec(0u32..70, 1..50),
+        ) {
+            let set = v.iter().copied().collect::<std::collections::BTreeSet<_>>();
+            let x = OrderedCollection::from(v);
+            for q in queries {
+                proptest::prop_assert_eq!(x.find_gte(q), set.range(q..).next());
+                proptest::prop_assert_eq!(x.find_lte(q), set.range(..=q).next_back());
+            }
+        }
+
+        /// Both ways of building the layout must give the very same one.
+        #[test]
+        #[cfg_attr(miri, ignore)]
+        fn sorted_vec_same_layout(mut v in proptest::collection::vec(0u32..64, 0..300)) {
+            v.sort_unstable();
+            let x = OrderedCollection::from_sorted_iter(v.iter().copied());
+            let y = OrderedCollection::from_sorted_vec(v);
+            proptest::prop_assert_eq!(x.as_eytzinger_slice(), y.as_eytzinger_slice());
+        }
+    }
+
+    #[test]
+    fn complete_exact() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+        assert_eq!(x.find_gte(1), Some(&1));
+        assert_eq!(x.find_gte(2), Some(&2));
+        assert_eq!(x.find_gte(4), Some(&4));
+        assert_eq!(x.find_gte(8), Some(&8));
+        assert_eq!(x.find_gte(16), Some(&16));
+        assert_eq!(x.find_gte(32), Some(&32));
+        assert_eq!(x.find_gte(64), Some(&64));
+    }
+
+    #[test]
+    fn complete_approximate() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+        assert_eq!(x.find_gte(0), Some(&1));
+        assert_eq!(x.find_gte(3), Some(&4));
+        assert_eq!(x.find_gte(5), Some(&8));
+        assert_eq!(x.find_gte(6), Some(&8));
+        assert_eq!(x.find_gte(7), Some(&8));
+        for i in 9..16 {
+            assert_eq!(x.find_gte(i), Some(&16));
+        }
+        for i in 17..32 {
+            assert_eq!(x.find_gte(i), Some(&32));
+        }
+        for i in 33..64 {
+            assert_eq!(x.find_gte(i), Some(&64));
+        }
+        assert_eq!(x.find_gte(65), None);
+    }
+
+    #[test]
+    fn unbalanced_exact() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64, 128, 256]);
+        assert_eq!(x.find_gte(1), Some(&1));
+        assert_eq!(x.find_gte(2), Some(&2));
+        assert_eq!(x.find_gte(4), Some(&4));
+        assert_eq!(x.find_gte(8), Some(&8));
+        assert_eq!(x.find_gte(16), Some(&16));
+        assert_eq!(x.find_gte(32), Some(&32));
+        assert_eq!(x.find_gte(64), Some(&64));
+        assert_eq!(x.find_gte(128), Some(&128));
+        assert_eq!(x.find_gte(256), Some(&256));
+    }
+
+    #[test]
+    fn unbalanced_approximate() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64, 128, 256]);
+        assert_eq!(x.find_gte(0), Some(&1));
+        assert_eq!(x.find_gte(3), Some(&4));
+        assert_eq!(x.find_gte(5), Some(&8));
+        assert_eq!(x.find_gte(6), Some(&8));
+        assert_eq!(x.find_gte(7), Some(&8));
+        for i in 9..16 {
+            assert_eq!(x.find_gte(i), Some(&16));
+        }
+        for i in 17..32 {
+            assert_eq!(x.find_gte(i), Some(&32));
+        }
+        for i in 33..64 {
+            assert_eq!(x.find_gte(i), Some(&64));
+        }
+        for i in 65..128 {
+            assert_eq!(x.find_gte(i), Some(&128));
+        }
+        for i in 129..256 {
+            assert_eq!(x.find_gte(i), Some(&256));
+        }
+        assert_eq!(x.find_gte(257), None);
+    }
+
+    #[test]
+    fn branchy_matches_branchless() {
+        for n in [0, 1, 2, 7, 9, 100, 1000] {
+            let mut v = pseudorandom(n as u64).take(n).collect::<Vec<_>>();
+            v.extend_from_within(..n / 2);
+            let x = OrderedCollection::from(v);
+            for q in 0..1030 {
+                assert_eq!(x.find_gte_branchy(q), x.find_gte(q));
+            }
+        }
+    }
+
+    #[cfg(feature = "simd")]
+    #[test]
+    fn simd_matches_scalar() {
+        for n in (0..70).chain([100, 1000, 5000]) {
+            let mut v = pseudorandom(n as u64).take(n).collect::<Vec<_>>();
+            v.extend_from_within(..n / 2);
+
+            // spread the values over the whole domain to exercise unsigned comparisons
+            let x = OrderedCollection::from(v.iter().map(|&v| v << 22).collect::<Vec<u32>>());
+            for q in pseudorandom(7).take(2000).map(|q| q << 22 | q) {
+                assert_eq!(x.find_gte_simd(q), x.find_gte(q), "n = {}, q = {}", n, q);
+            }
+            assert_eq!(x.find_gte_simd(u32::MAX), x.find_gte(u32::MAX));
+
+            let x = OrderedCollection::from(v.iter().map(|&v| (v << 6) as u16).collect::<Vec<_>>());
+            for q in pseudorandom(7).take(2000).map(|q| (q << 6 | q) as u16) {
+                assert_eq!(x.find_gte_simd(q), x.find_gte(q), "n = {}, q = {}", n, q);
+            }
+            assert_eq!(x.find_gte_simd(u16::MAX), x.find_gte(u16::MAX));
+        }
+    }
+
+    #[test]
+    fn signed_values() {
+        let x = OrderedCollection::from(vec![9, -1, 3, -8, 0]);
+        assert_eq!(x.find_gte(i32::MIN), Some(&-8));
+        assert_eq!(x.find_gte(-9), Some(&-8));
+        assert_eq!(x.find_gte(-8), Some(&-8));
+        assert_eq!(x.find_gte(-5), Some(&-1));
+        assert_eq!(x.find_gte(-1), Some(&-1));
+        assert_eq!(x.find_gte(0), Some(&0));
+        assert_eq!(x.find_gte(1), Some(&3));
+        assert_eq!(x.find_gte(9), Some(&9));
+        assert_eq!(x.find_gte(10), None);
+        assert_eq!(x.find_gte(i32::MAX), None);
+        assert_eq!(x.find_lte(-9), None);
+        assert_eq!(x.find_lte(-1), Some(&-1));
+        assert_eq!(x.rank(0), 2);
+
+        // all the values of a small signed type, across the sign boundary and at both extremes
+        let x = OrderedCollection::from_sorted_iter((i8::MIN..=i8::MAX).step_by(3));
+        for q in i8::MIN..=i8::MAX {
+            let expected = (i8::MIN..=i8::MAX).step_by(3).find(|&v| v >= q);
+            assert_eq!(x.find_gte(q).copied(), expected, "q = {}", q);
+        }
+
+        let x = OrderedCollection::from(vec![isize::MIN, -1, 0, 1, isize::MAX]);
+        assert_eq!(x.find_gte(isize::MIN + 1), Some(&-1));
+        assert_eq!(x.find_gte(1), Some(&1));
+        assert_eq!(x.find_gte(2), Some(&isize::MAX));
+    }
+
+    /// `find_gte_linear_tail` is the alternative search that the benchmarks compare with
+    /// `find_gte`, so it must return the very same element on random collections with duplicates.
+    #[test]
+    fn linear_tail_matches_find_gte() {
+        fn check<const LEVELS: u32>(x: &OrderedCollection<u32>, q: u32) {
+            let expected = x.find_gte(q);
+            let found = x.find_gte_linear_tail::<LEVELS, _>(q);
+            // with duplicates both must return the very same element
+            assert_eq!(
+                found.map(|v| v as *const u32),
+                expected.map(|v| v as *const u32)
+            );
+        }
+
+        for n in (0..140).chain([1000, 4095, 4096, 5000]) {
+            let mut v = pseudorandom(n as u64).take(n).collect::<Vec<_>>();
+            v.extend_from_within(..n / 3);
+            let x = OrderedCollection::from(v);
+            for q in 0..1030 {
+                check::<0>(&x, q);
+                check::<1>(&x, q);
+                check::<2>(&x, q);
+                check::<4>(&x, q);
+                check::<7>(&x, q);
+                check::<64>(&x, q);
+            }
+        }
+    }
+
+    #[test]
+    fn prefetch_does_not_affect_results() {
+        for n in 0..100 {
+            let x = OrderedCollection::from(pseudorandom(n as u64).take(n).collect::<Vec<_>>());
+            for q in 0..1025 {
+                assert_eq!(x.find_gte(q), x.find_gte_without_prefetch(q));
+                assert_eq!(x.find_gte(q), x.find_gte_with_prefetch_levels::<1, _>(q));
+                assert_eq!(x.find_gte(q), x.find_gte_with_prefetch_levels::<5, _>(q));
+            }
+        }
+    }
+
+    #[test]
+    fn lte_exact() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64, 128, 256]);
+        for v in [1, 2, 4, 8, 16, 32, 64, 128, 256] {
+            assert_eq!(x.find_lte(v), Some(&v));
+        }
+    }
+
+    #[test]
+    fn lte_approximate() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
+        assert_eq!(x.find_lte(0), None);
+        assert_eq!(x.find_lte(3), Some(&2));
+        assert_eq!(x.find_lte(5), Some(&4));
+        assert_eq!(x.find_lte(6), Some(&4));
+        assert_eq!(x.find_lte(7), Some(&4));
+        for i in 9..16 {
+            assert_eq!(x.find_lte(i), Some(&8));
+        }
+        for i in 17..32 {
+            assert_eq!(x.find_lte(i), Some(&16));
+        }
+        for i in 33..64 {
+            assert_eq!(x.find_lte(i), Some(&32));
+        }
+        assert_eq!(x.find_lte(65), Some(&64));
+    }
+
+    #[test]
+    fn strict_boundaries() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+        assert_eq!(x.find_gt(0), Some(&1));
+        assert_eq!(x.find_gt(1), Some(&2));
+        assert_eq!(x.find_gt(2), Some(&4));
+        assert_eq!(x.find_gt(7), Some(&8));
+        assert_eq!(x.find_gt(8), None);
+        assert_eq!(x.find_lt(0), None);
+        assert_eq!(x.find_lt(1), None);
+        assert_eq!(x.find_lt(2), Some(&1));
+        assert_eq!(x.find_lt(5), Some(&4));
+        assert_eq!(x.find_lt(8), Some(&4));
+        assert_eq!(x.find_lt(9), Some(&8));
+    }
+
+    #[test]
+    fn strict_duplicates() {
+        let x = OrderedCollection::from(vec![2, 2, 2]);
+        assert_eq!(x.find_gt(1), Some(&2));
+        assert_eq!(x.find_gt(2), None);
+        assert_eq!(x.find_lt(2), None);
+        assert_eq!(x.find_lt(3), Some(&2));
+
+        let x = OrderedCollection::from(vec![1, 3, 3, 3, 3, 3, 5, 5, 7]);
+        assert_eq!(x.find_gt(3), Some(&5));
+        assert_eq!(x.find_gt(5), Some(&7));
+        assert_eq!(x.find_lt(3), Some(&1));
+        assert_eq!(x.find_lt(5), Some(&3));
+        assert_eq!(x.find_lt(7), Some(&5));
+    }
+
+    #[test]
+    fn try_sorted() {
+        for n in 0..64 {
+            let v = (0..n).map(|v| v / 2).collect::<Vec<_>>();
+            let x = OrderedCollection::try_from_sorted_iter(v.iter().copied()).unwrap();
+            assert_eq!(x.iter().copied().collect::<Vec<_>>(), v);
+        }
+    }
+
+    #[test]
+    fn try_unsorted() {
+        use alloc::string::ToString;
+
+        for n in 2..64 {
+            for index in 1..n {
+                let mut v = (0..n).collect::<Vec<_>>();
+                v.swap(index - 1, index);
+                let err = OrderedCollection::try_from_sorted_iter(v).unwrap_err();
+                assert_eq!(err.index(), index);
+            }
+        }
+        let err = OrderedCollection::try_from_sorted_iter(vec![3, 2, 1]).unwrap_err();
+        assert_eq!(err.index(), 1);
+        assert_eq!(
+            err.to_string(),
+            "element at index 1 is smaller than its predecessor"
+        );
+    }
+
+    #[test]
+    fn partial_ord_floats() {
+        let v = vec![
+            3.5f32,
+            -0.0,
+            f32::INFINITY,
+            1.25,
+            -7.0,
+            f32::NEG_INFINITY,
+            1.25,
+        ];
+        let x = OrderedCollection::try_from_partial_ord(v.clone()).unwrap();
+        let mut sorted = v;
+        sorted.sort_unstable_by(f32::total_cmp);
+        assert_eq!(x.iter().copied().collect::<Vec<_>>(), sorted);
+        for q in [-8.0, -7.0, 0.0, 1.0, 1.25, 2.0, 4.0, f32::INFINITY] {
+            let expected = sorted.iter().find(|&&v| v >= q);
+            assert_eq!(x.find_gte_by(q, |v, x| v.partial_cmp(x).unwrap()), expected);
+        }
+
+        assert!(OrderedCollection::<f64>::try_from_partial_ord(Vec::new()).is_ok());
+    }
+
+    #[test]
+    fn partial_ord_signed_zeros() {
+        let by_float = |v: &f64, x: &f64| v.partial_cmp(x).unwrap();
+        for v in [
+            vec![0.0f64, -0.0],
+            vec![-0.0, 0.0],
+            vec![1.0, 0.0, -1.0, -0.0],
+        ] {
+            let x = OrderedCollection::try_from_partial_ord(v.clone()).unwrap();
+            for q in [0.0, -0.0] {
+                assert_eq!(x.find_gte_by(q, by_float), Some(&0.0), "{:?}, q = {}", v, q);
+            }
+            assert_eq!(x.find_gte_by(-0.5, by_float), Some(&0.0));
+            assert_eq!(x.iter().filter(|&&v| v == 0.0).count(), 2);
+        }
+    }
+
+    #[test]
+    fn partial_ord_nan() {
+        use alloc::string::ToString;
+
+        let err = OrderedCollection::try_from_partial_ord(vec![1.0, 2.0, f64::NAN]).unwrap_err();
+        assert_eq!(err.index(), 2);
+        assert_eq!(
+            err.to_string(),
+            "element at index 2 can't be compared with other elements"
+        );
+
+        // a newtype whose `PartialOrd` forwards to the float
+        #[derive(Debug, PartialEq, PartialOrd)]
+        struct Float(f32);
+        let v = vec![Float(1.0), Float(f32::NAN), Float(0.5), Float(f32::NAN)];
+        let err = OrderedCollection::try_from_partial_ord(v).unwrap_err();
+        assert_eq!(err.index(), 1);
+    }
+
+    #[test]
+    fn never_calls_default() {
+        use core::sync::atomic::{AtomicUsize, Ordering::Relaxed};
+
+        static DEFAULTS: AtomicUsize = AtomicUsize::new(0);
+
+        #[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord)]
+        struct Counted(u32);
+        impl Default for Counted {
+            fn default() -> Self {
+                DEFAULTS.fetch_add(1, Relaxed);
+                Counted(0)
+            }
+        }
+
+        let mut x = OrderedCollection::from((0..100).map(Counted).collect::<Vec<_>>());
+        assert_eq!(x.find_gte(Counted(0)), Some(&Counted(0)));
+        assert_eq!(x.find_gte(Counted(100)), None);
+        x.insert(Counted(7));
+        x.extend([Counted(3)]);
+        let _ = x.clone();
+        let _ = OrderedCollection::from_sorted_iter(x.iter().cloned());
+        assert_eq!(DEFAULTS.load(Relaxed), 0);
+    }
+
+    #[test]
+    fn sorted_vec_matches_sorted_iter() {
+        for n in (0..300).chain([1023, 1024, 1025, 5000]) {
+            let x = OrderedCollection::from_sorted_iter(0..n);
+            let y = OrderedCollection::from_sorted_vec((0..n).collect());
+            assert_eq!(x.as_eytzinger_slice(), y.as_eytzinger_slice(), "n = {}", n);
+            assert_eq!((x.min, x.max), (y.min, y.max));
+        }
+
+        // elements with destructors are moved, not copied
+        let v = (0..100)
+            .map(|v| alloc::format!("{:03}", v))
+            .collect::<Vec<_>>();
+        let x = OrderedCollection::from_sorted_vec(v.clone());
+        assert_eq!(x.to_sorted_vec(), v);
+    }
+
+    #[test]
+    fn check_macro() {
+        let x: OrderedCollection<u32> = ordered_collection![8, 1, 4, 2, 4,];
+        assert_eq!(x.len(), 5);
+        assert_eq!(x.to_sorted_vec(), vec![1, 2, 4, 4, 8]);
+        assert_eq!(x.find_gte(3), Some(&4));
+        assert_eq!(x.find_gte(9), None);
+
+        let x: OrderedCollection<u32> = ordered_collection![];
+        assert!(x.is_empty());
+    }
+
+    #[test]
+    fn check_len() {
+        let x = OrderedCollection::from(vec![1, 2, 3]);
+        assert_eq!(x.len(), 3);
+        assert!(!x.is_empty());
+    }
+
+    #[test]
+    fn check_default() {
+        let x = OrderedCollection::<u32>::default();
+        assert_eq!(x.len(), 0);
+        assert_eq!(x.find_gte(5), None);
+        assert_eq!(x.find_gte(0), None);
+        assert_eq!(x.find_gte_branchy(5), None);
+        assert_eq!(x.find_lte(5), None);
+        assert_eq!(x.find_gt(5), None);
+        assert_eq!(x.find_lt(5), None);
+        assert!(!x.contains(5));
+        assert_eq!(x.first(), None);
+        assert_eq!(x, OrderedCollection::from(Vec::new()));
+    }
+
+    #[test]
+    fn check_from_sorted_iter_copied() {
+        let v = vec![1u64, 2, 4, 8];
+        let x = OrderedCollection::from_sorted_iter_copied(&v);
+        assert_eq!(x, OrderedCollection::from_sorted_iter(v.iter().copied()));
+        assert_eq!(x.find_gte(3), Some(&4));
+        drop(v);
+        // the collection owns copies of the elements
+        assert_eq!(x.len(), 4);
+    }
+
+    #[test]
+    fn check_len_empty() {
+        let x = OrderedCollection::<u32>::from(Vec::new());
+        assert_eq!(x.len(), 0);
+        assert!(x.is_empty());
+        assert_eq!(x.find_gte(0), None);
+
+        let x = OrderedCollection::<u32>::from_sorted_iter(core::iter::empty());
+        assert_eq!(x.len(), 0);
+        assert!(x.is_empty());
+    }
+
+    /// `[0]` of an empty collection is uninitialized, and must not be returned by any lookup
+    #[test]
+    fn empty_lookups() {
+        let x = OrderedCollection::<u32>::from_sorted_iter(core::iter::empty());
+        for q in [0, 1, u32::MAX] {
+            assert_eq!(x.find_gte(q), None);
+            assert_eq!(x.find_gte_ref(&q), None);
+            assert_eq!(x.find_gte_index(q), None);
+            assert_eq!(x.find_gte_without_prefetch(q), None);
+            assert_eq!(x.find_gte_with_prefetch_levels::<4, _>(q), None);
+            assert_eq!(x.find_gte_linear_tail::<3, _>(q), None);
+            assert_eq!(x.find_gte_branchy(q), None);
+            assert_eq!(x.find_gte_by(q, |v, q| v.cmp(q)), None);
+            assert_eq!(x.find_gte_by_key(q, |&v| v), None);
+            assert_eq!(x.find_gte_with_rank(q), None);
+            assert_eq!(x.find_first_true(|_| true), None);
+            assert_eq!(x.find_lte(q), None);
+            assert_eq!(x.find_gt(q), None);
+            assert_eq!(x.find_lt(q), None);
+            assert_eq!(x.find_nearest_by(q, |&v, &q| v.abs_diff(q)), None);
+            assert_eq!(x.locate(q), Match::Empty);
+            assert_eq!(x.rank(q), 0);
+            assert!(!x.contains(q));
+            assert!(!x.contains_range(0, q));
+            assert_eq!(x.range(0, q).next(), None);
+            assert_eq!(x.cursor_gte(q).next(), None);
+            let mut out = [Some(&0)];
+            x.find_gte_batch(&[q], &mut out);
+            assert_eq!(out, [None]);
+        }
+        assert_eq!(x.first(), None);
+        assert_eq!(x.last(), None);
+        assert_eq!(x.select(0), None);
+        assert_eq!(x.iter().next(), None);
+        assert_eq!(
+            search_eytzinger::<u32, u32>(x.as_eytzinger_slice(), &0),
+            None
+        );
+        assert_eq!(
+            OrderedArray::<u32, 0>::from_sorted_array([]).find_gte(0),
+            None
+        );
+    }
+
+    #[test]
+    fn check_sorted() {
+        use alloc::collections::BTreeSet;
+
+        let set = pseudorandom(5).take(500).collect::<BTreeSet<_>>();
+        let sorted = OrderedCollection::from(Sorted(set.clone()));
+        let unsorted = set.iter().rev().copied().collect::<OrderedCollection<_>>();
+        assert_eq!(sorted, unsorted);
+        assert_eq!(sorted.len(), set.len());
+    }
+
+    #[test]
+    fn check_from_sorted_slice() {
+        let v = [1, 2, 4, 8, 16, 32, 64];
+        let x = OrderedCollection::from_sorted_slice(&v);
+        assert_eq!(v, [1, 2, 4, 8, 16, 32, 64]);
+        assert_eq!(x.find_gte(0), Some(&1));
+        assert_eq!(x.find_gte(5), Some(&8));
+        assert_eq!(x.find_gte(64), Some(&64));
+        assert_eq!(x.find_gte(65), None);
+    }
+
+    #[test]
+    fn check_from_iter() {
+        let x: OrderedCollection<_> = [64, 1, 16, 2, 8, 32, 4].iter().copied().collect();
+        assert_eq!(x.len(), 7);
+        for v in [1, 2, 4, 8, 16, 32, 64] {
+            assert_eq!(x.find_gte(v), Some(&v));
+        }
+        assert_eq!(x.find_gte(65), None);
+    }
+
+    #[test]
+    fn batch_matches_single() {
+        for n in [0, 1, 2, 7, 9, 100, 1000] {
+            let mut v = pseudorandom(n as u64).take(n).collect::<Vec<_>>();
+            v.extend_from_within(..n / 2);
+            let x = OrderedCollection::from(v);
+            let queries = pseudorandom(42).take(1001).collect::<Vec<_>>();
+            let mut out = vec![None; queries.len()];
+            x.find_gte_batch(&queries, &mut out);
+            for (q, o) in queries.iter().zip(&out) {
+                assert_eq!(*o, x.find_gte(*q));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn batch_length_mismatch() {
+        let x = OrderedCollection::from(vec![1, 2, 3]);
+        x.find_gte_batch(&[1, 2], &mut [None]);
+    }
+
+    /// A one-off comparator over a collection sorted in reverse, which has no `Ord` to fall back to.
+    #[test]
+    fn custom_order() {
+        #[derive(Debug, PartialEq)]
+        struct Unordered(u32);
+
+        let desc = |v: &Unordered, x: &Unordered| x.0.cmp(&v.0);
+        let x = OrderedCollection::from_sorted_iter_by(
+            [256, 128, 64, 32, 16, 8, 4, 2, 1]
+                .iter()
+                .map(|&v| Unordered(v)),
+            desc,
+        );
+        assert_eq!(x.find_gte_by(Unordered(300), desc), Some(&Unordered(256)));
+        assert_eq!(x.find_gte_by(Unordered(256), desc), Some(&Unordered(256)));
+        assert_eq!(x.find_gte_by(Unordered(100), desc), Some(&Unordered(64)));
+        assert_eq!(x.find_gte_by(Unordered(3), desc), Some(&Unordered(2)));
+        assert_eq!(x.find_gte_by(Unordered(1), desc), Some(&Unordered(1)));
+        assert_eq!(x.find_gte_by(Unordered(0), desc), None);
+    }
+
+    #[test]
+    fn reverse_order() {
+        use core::cmp::Reverse;
+
+        let x = OrderedCollection::from(vec![Reverse(1), Reverse(64), Reverse(8)]);
+        assert_eq!(x.find_gte(Reverse(65)), Some(&Reverse(64)));
+        assert_eq!(x.find_gte(Reverse(7)), Some(&Reverse(1)));
+        assert_eq!(x.find_gte(Reverse(0)), None);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "not sorted")]
+    fn custom_order_unsorted() {
+        let _ = OrderedCollection::from_sorted_iter_by(vec![1, 2, 3], |a: &u32, b: &u32| b.cmp(a));
+    }
+
+    #[test]
+    fn key_order() {
+        #[derive(Debug, PartialEq)]
+        struct Event {
+            ts: u64,
+            payload: Vec<u8>,
+        }
+
+        let events = [10, 20, 20, 40].iter().map(|&ts| Event {
+            ts,
+            payload: vec![ts as u8],
+        });
+        let ts = |e: &Event| e.ts;
+        let x = OrderedCollection::from_sorted_iter_by_key(events, ts);
+        assert_eq!(x.find_gte_by_key(0, ts).map(|e| e.ts), Some(10));
+        assert_eq!(x.find_gte_by_key(10, ts).map(|e| e.ts), Some(10));
+        assert_eq!(x.find_gte_by_key(11, ts).map(|e| e.ts), Some(20));
+        assert_eq!(
+            x.find_gte_by_key(21, ts).map(|e| &e.payload),
+            Some(&vec![40])
+        );
+        assert_eq!(x.find_gte_by_key(41, ts), None);
+    }
+
+    #[test]
+    fn check_find_first_true() {
+        for n in 0..100 {
+            let v = pseudorandom(n as u64).take(n).collect::<Vec<_>>();
+            let x = OrderedCollection::from(v);
+            for q in 0..1025 {
+                assert_eq!(x.find_first_true(|&v| v >= q), x.find_gte(q));
+                assert_eq!(x.find_first_true(|&v| v > q), x.find_gt(q));
+            }
+            assert_eq!(x.find_first_true(|_| true), x.first());
+            assert_eq!(x.find_first_true(|_| false), None);
+        }
+
+        // intervals sorted by their start, looking for the first one that ends after a point. This
+        // is monotone because the intervals don't overlap.
+        let intervals = vec![(0, 5), (10, 12), (12, 20), (30, 31)];
+        let x = OrderedCollection::from_sorted_iter(intervals);
+        assert_eq!(x.find_first_true(|&(_, end)| end > 3), Some(&(0, 5)));
+        assert_eq!(x.find_first_true(|&(_, end)| end > 5), Some(&(10, 12)));
+        assert_eq!(x.find_first_true(|&(_, end)| end > 12), Some(&(12, 20)));
+        assert_eq!(x.find_first_true(|&(_, end)| end > 31), None);
+    }
+
+    #[test]
+    fn check_first_last() {
+        for n in 1..100 {
+            let x = OrderedCollection::from_sorted_iter(0..n);
+            assert_eq!(x.first(), Some(&0));
+            assert_eq!(x.last(), Some(&(n - 1)));
+        }
+
+        let x = OrderedCollection::<u32>::from(vec![]);
+        assert_eq!(x.first(), None);
+        assert_eq!(x.last(), None);
+    }
+
+    #[test]
+    fn check_nearest() {
+        let x = OrderedCollection::from(vec![10u32, 20, 40]);
+        let dist = |v: &u32, x: &u32| v.abs_diff(*x);
+        // below
+        assert_eq!(x.find_nearest_by(12, dist), Some(&10));
+        assert_eq!(x.find_nearest_by(0, dist), Some(&10));
+        // above
+        assert_eq!(x.find_nearest_by(18, dist), Some(&20));
+        assert_eq!(x.find_nearest_by(100, dist), Some(&40));
+        // tied
+        assert_eq!(x.find_nearest_by(15, dist), Some(&10));
+        assert_eq!(x.find_nearest_by(30, dist), Some(&20));
+        // exact
+        assert_eq!(x.find_nearest_by(20, dist), Some(&20));
+
+        let x = OrderedCollection::<u32>::from(Vec::new());
+        assert_eq!(x.find_nearest_by(0, dist), None);
+    }
+
+    #[test]
+    fn check_find_within() {
+        let x = OrderedCollection::from(vec![10u32, 20, 40]);
+        // inside
+        assert_eq!(x.find_within(12, 5), Some(&10));
+        assert_eq!(x.find_within(37, 5), Some(&40));
+        assert_eq!(x.find_within(20, 0), Some(&20));
+        // exactly on the tolerance
+        assert_eq!(x.find_within(5, 5), Some(&10));
+        assert_eq!(x.find_within(45, 5), Some(&40));
+        // tied, both within the tolerance
+        assert_eq!(x.find_within(30, 10), Some(&20));
+        // outside
+        assert_eq!(x.find_within(4, 5), None);
+        assert_eq!(x.find_within(30, 9), None);
+        assert_eq!(x.find_within(u32::MAX, 5), None);
+
+        let x = OrderedCollection::from(vec![-10i32, 0, 10]);
+        assert_eq!(x.find_within(-13, 3), Some(&-10));
+        assert_eq!(x.find_within(-14, 3), None);
+
+        let x = OrderedCollection::<u32>::from(Vec::new());
+        assert_eq!(x.find_within(0, 100), None);
+    }
+
+    #[test]
+    fn check_locate() {
+        let x = OrderedCollection::from(vec![10, 20, 20, 30]);
+        // exact
+        assert_eq!(x.locate(10), Match::Exact(&10));
+        assert_eq!(x.locate(20), Match::Exact(&20));
+        assert_eq!(x.locate(30), Match::Exact(&30));
+        // gaps
+        assert_eq!(
+            x.locate(15),
+            Match::Between {
+                lower: Some(&10),
+                upper: Some(&20)
+            }
+        );
+        assert_eq!(
+            x.locate(25),
+            Match::Between {
+                lower: Some(&20),
+                upper: Some(&30)
+            }
+        );
+        // below min
+        assert_eq!(
+            x.locate(5),
+            Match::Between {
+                lower: None,
+                upper: Some(&10)
+            }
+        );
+        // above max
+        assert_eq!(
+            x.locate(35),
+            Match::Between {
+                lower: Some(&30),
+                upper: None
+            }
+        );
+
+        let x = OrderedCollection::<u32>::default();
+        assert_eq!(x.locate(0), Match::Empty);
+    }
+
+    #[test]
+    fn check_rank() {
+        for n in 0..100 {
+            let x = OrderedCollection::from(
+                pseudorandom(n as u64)
+                    .take(n)
+                    .map(|v| v % 128)
+                    .collect::<Vec<_>>(),
+            );
+            let v = x.iter().copied().collect::<Vec<_>>();
+            for q in 0..130 {
+                assert_eq!(x.rank(q), v.iter().filter(|&&v| v < q).count());
+            }
+        }
+    }
+
+    #[test]
+    fn check_find_gte_ref() {
+        use alloc::string::{String, ToString};
+
+        for n in 0..100 {
+            let x = OrderedCollection::from(pseudorandom(n as u64).take(n).collect::<Vec<_>>());
+            for q in 0..1025 {
+                assert_eq!(x.find_gte_ref(&q), x.find_gte(q));
+            }
+        }
+
+        let words = ["delta", "alpha", "charlie", "bravo"];
+        let x = OrderedCollection::from(words.iter().map(|w| w.to_string()).collect::<Vec<_>>());
+        assert_eq!(x.find_gte_ref("b").map(String::as_str), Some("bravo"));
+        assert_eq!(x.find_gte_ref("bravo").map(String::as_str), Some("bravo"));
+        assert_eq!(x.find_gte_ref("c").map(String::as_str), Some("charlie"));
+        assert_eq!(x.find_gte_ref("e"), None);
+        assert_eq!(
+            x.find_gte_ref(&"b".to_string()),
+            x.find_gte("b".to_string())
+        );
+    }
+
+    #[test]
+    fn check_find_gte_index() {
+        for n in 0..100 {
+            let x = OrderedCollection::from(pseudorandom(n as u64).take(n).collect::<Vec<_>>());
+            let items = x.as_eytzinger_slice();
+            for q in 0..1025 {
+                assert_eq!(x.find_gte_index(q).map(|i| &items[i]), x.find_gte(q));
+            }
+        }
+    }
+
+    #[test]
+    fn check_find_gte_raw() {
+        for n in 0..100 {
+            let x = OrderedCollection::from(pseudorandom(n as u64).take(n).collect::<Vec<_>>());
+            for q in 0..1025 {
+                let mut i = usize::MAX;
+                let found = x.find_gte_raw(q, &mut i);
+                assert_eq!(found, x.find_gte(q).is_some());
+                if found {
+                    assert!(core::ptr::eq(x.raw(i), x.find_gte(q).unwrap()));
+                } else {
+                    assert_eq!(i, usize::MAX);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn check_find_gte_or_last() {
+        for n in 0..100 {
+            let x = OrderedCollection::from(pseudorandom(n as u64).take(n).collect::<Vec<_>>());
+            for q in 0..1030 {
+                // in range, or above the largest element
+                let expected = x.find_gte(q).or(x.last());
+                assert_eq!(x.find_gte_or_last(q), expected);
+            }
+        }
+
+        let x = OrderedCollection::from(vec![3, 5, 5]);
+        assert_eq!(x.find_gte_or_last(0), Some(&3));
+        assert_eq!(x.find_gte_or_last(4), Some(&5));
+        assert_eq!(x.find_gte_or_last(5), Some(&5));
+        assert_eq!(x.find_gte_or_last(6), Some(&5));
+
+        let x = OrderedCollection::<u32>::default();
+        assert_eq!(x.find_gte_or_last(0), None);
+    }
+
+    #[test]
+    fn leftmost_duplicate() {
+        let x = OrderedCollection::from(vec![2, 2, 2, 2]);
+        for q in 0..3 {
+            let (rank, v) = x.find_gte_with_rank(q).unwrap();
+            assert_eq!(rank, 0);
+            assert!(core::ptr::eq(v, x.iter().next().unwrap()));
+            assert!(core::ptr::eq(x.find_gte(q).unwrap(), v));
+        }
+
+        for n in 0..100 {
+            let x = OrderedCollection::from(
+                pseudorandom(n as u64)
+                    .take(n)
+                    .map(|v| v % 8)
+                    .collect::<Vec<_>>(),
+            );
+            for q in 0..9 {
+                let first = x.iter().position(|&v| v >= q);
+                assert_eq!(x.find_gte_with_rank(q).map(|(rank, _)| rank), first);
+                if let Some(rank) = first {
+                    assert!(core::ptr::eq(x.find_gte(q).unwrap(), &x[rank]));
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn check_find_gte_mut() {
+        #[derive(Debug, PartialEq, Eq)]
+        struct Record(u32, &'static str);
+        impl PartialOrd for Record {
+            fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+                Some(self.cmp(other))
+            }
+        }
+        impl Ord for Record {
+            fn cmp(&self, other: &Self) -> Ordering {
+                self.0.cmp(&other.0)
+            }
+        }
+        impl Borrow<u32> for Record {
+            fn borrow(&self) -> &u32 {
+                &self.0
+            }
+        }
+
+        let mut x =
+            OrderedCollection::from((0..100).map(|k| Record(2 * k, "")).collect::<Vec<_>>());
+        for q in (0..200).step_by(3) {
+            x.find_gte_mut(q).unwrap().1 = "hit";
+        }
+        assert!(x.find_gte_mut(199).is_none());
+        for (k, record) in x.iter().enumerate() {
+            // query `q` hits the record with key `2 * k >= q`
+            let hit = (0..200usize).step_by(3).any(|q| q.div_ceil(2) == k);
+            assert_eq!(record.0, 2 * k as u32);
+            assert_eq!(record.1, if hit { "hit" } else { "" }, "k = {}", k);
+            assert_eq!(x.find_gte(record.0), Some(record));
+        }
+    }
+
+    #[test]
+    fn search_eytzinger_hand_built() {
+        // the layout of [10, 20, 30, 40, 50, 60]
+        let items = [40, 20, 60, 10, 30, 50];
+        let expected = [
+            (5, Some(3)),
+            (10, Some(3)),
+            (11, Some(1)),
+            (25, Some(4)),
+            (35, Some(0)),
+            (45, Some(5)),
+            (55, Some(2)),
+            (60, Some(2)),
+            (61, None),
+        ];
+        for (q, i) in expected {
+            assert_eq!(search_eytzinger(&items, &q), i, "query {}", q);
+        }
+        assert_eq!(search_eytzinger::<u32, u32>(&[], &0), None);
+    }
+
+    #[test]
+    fn check_raw() {
+        for n in 0..100 {
+            let x = OrderedCollection::from(pseudorandom(n as u64).take(n).collect::<Vec<_>>());
+            for (i, value) in x.as_eytzinger_slice().iter().enumerate() {
+                assert!(core::ptr::eq(x.raw(i), value));
+            }
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn raw_out_of_bounds() {
+        let x = OrderedCollection::from(vec![1, 2, 3]);
+        x.raw(3);
+    }
+
+    #[test]
+    fn check_find_gte_with_rank() {
+        for n in 0..100 {
+            let mut v = pseudorandom(n as u64)
+                .take(n)
+                .map(|v| v % 128)
+                .collect::<Vec<_>>();
+            v.sort_unstable();
+            let x = OrderedCollection::from_sorted_iter(v.iter().copied());
+            for q in 0..130 {
+                let rank = v.partition_point(|&v| v < q);
+                assert_eq!(x.find_gte_with_rank(q), v.get(rank).map(|v| (rank, v)));
+            }
+        }
+    }
+
+    #[test]
+    fn check_find_gte_and_rank() {
+        for n in 0..100 {
+            let v = pseudorandom(n as u64)
+                .take(n)
+                .map(|v| v % 128)
+                .collect::<Vec<_>>();
+            let x = OrderedCollection::from(v.clone());
+            for q in 0..130 {
+                let expected = v.iter().filter(|&&v| v >= q).min();
+                let smaller = v.iter().filter(|&&v| v < q).count();
+                assert_eq!(x.find_gte_and_rank(q), (expected, smaller));
+            }
+        }
+    }
+
+    #[test]
+    fn check_cursor() {
+        let x = OrderedCollection::from(vec![10, 20, 30, 40]);
+
+        // walking forward past the end, then back across the starting point
+        let mut cursor = x.cursor_gte(25);
+        assert_eq!(cursor.next(), Some(&30));
+        assert_eq!(cursor.next(), Some(&40));
+        assert_eq!(cursor.next(), None);
+        assert_eq!(cursor.next(), None);
+        assert_eq!(cursor.prev(), Some(&40));
+        assert_eq!(cursor.prev(), Some(&30));
+        assert_eq!(cursor.prev(), Some(&20));
+        assert_eq!(cursor.prev(), Some(&10));
+        assert_eq!(cursor.prev(), None);
+        assert_eq!(cursor.next(), Some(&10));
+
+        // positioned past the end
+        let mut cursor = x.cursor_gte(41);
+        assert_eq!(cursor.next(), None);
+        assert_eq!(cursor.prev(), Some(&40));
+
+        // positioned before the start
+        let mut cursor = x.cursor_gte(0);
+        assert_eq!(cursor.prev(), None);
+        assert_eq!(cursor.copied().collect::<Vec<_>>(), [10, 20, 30, 40]);
+
+        let empty = OrderedCollection::<u32>::default();
+        let mut cursor = empty.cursor_gte(0);
+        assert_eq!(cursor.next(), None);
+        assert_eq!(cursor.prev(), None);
+    }
+
+    #[test]
+    fn check_select() {
+        for n in 0..100 {
+            let mut v = pseudorandom(n as u64).take(n).collect::<Vec<_>>();
+            v.sort_unstable();
+            let x = OrderedCollection::from_sorted_iter(v.iter().copied());
+            for (k, value) in v.iter().enumerate() {
+                assert_eq!(x.select(k), Some(value));
+            }
+            assert_eq!(x.select(n), None);
+            assert_eq!(x.select(usize::MAX), None);
+        }
+    }
+
+    #[test]
+    fn check_index() {
+        let x = OrderedCollection::from(vec![8, 1, 4, 2]);
+        assert_eq!([x[0], x[1], x[2], x[3]], [1, 2, 4, 8]);
+        for k in 0..5 {
+            assert_eq!(x.get(k), x.select(k));
+        }
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 4 but the index is 4")]
+    fn index_out_of_bounds() {
+        let x = OrderedCollection::from(vec![8, 1, 4, 2]);
+        let _ = x[4];
+    }
+
+    #[test]
+    #[should_panic(expected = "the len is 0 but the index is 0")]
+    fn index_empty() {
+        let x = OrderedCollection::<u32>::default();
+        let _ = x[0];
+    }
+
+    #[test]
+    fn check_contains() {
+        let x = OrderedCollection::from(vec![1, 2, 4, 8]);
+        for i in 0..10 {
+            assert_eq!(x.contains(i), [1, 2, 4, 8].contains(&i), "{}", i);
+        }
+
+        let x = OrderedCollection::from(vec![3, 3, 3, 5, 5]);
+        assert!(!x.contains(2));
+        assert!(x.contains(3));
+        assert!(!x.contains(4));
+        assert!(x.contains(5));
+        assert!(!x.contains(6));
+
+        assert!(!OrderedCollection::<u32>::from(vec![]).contains(0));
+    }
+
+    #[test]
+    fn check_contains_range() {
+        let x = OrderedCollection::from(vec![10, 20, 30]);
+        // overlapping
+        assert!(x.contains_range(15, 25));
+        assert!(x.contains_range(0, 100));
+        assert!(x.contains_range(20, 20));
+        // abutting
+        assert!(x.contains_range(0, 10));
+        assert!(x.contains_range(30, 40));
+        assert!(x.contains_range(21, 30));
+        // missing
+        assert!(!x.contains_range(0, 9));
+        assert!(!x.contains_range(31, 40));
+        assert!(!x.contains_range(11, 19));
+        assert!(!x.contains_range(25, 15));
+        assert!(!x.contains_range(20, 10));
+
+        assert!(!OrderedCollection::<u32>::default().contains_range(0, u32::MAX));
+
+        for n in 0..50 {
+            let x = OrderedCollection::from(pseudorandom(n as u64).take(n).collect::<Vec<_>>());
+            for lo in (0..1025).step_by(7) {
+                for hi in (0..1025).step_by(13) {
+                    assert_eq!(x.contains_range(lo, hi), x.range(lo, hi).next().is_some());
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn check_into_iter() {
+        let expected = vec![1, 2, 4, 8, 16, 32, 64, 128, 256];
+        let mut values = OrderedCollection::from_sorted_iter(expected.clone())
+            .into_iter()
+            .collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn check_into_iter_sorted() {
+        let coll = OrderedCollection::from(vec![8, 1, 4, 2, 2, 16]);
+        assert_eq!(
+            coll.into_iter().collect::<Vec<_>>(),
+            vec![1, 2, 2, 4, 8, 16]
+        );
+
+        let coll = OrderedCollection::from(vec![Box::new(3), Box::new(1), Box::new(2)]);
+        let values = coll.into_iter().map(|v| *v).collect::<Vec<_>>();
+        assert_eq!(values, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn check_into_sorted_vec() {
+        for n in 0..100 {
+            let coll = OrderedCollection::from(pseudorandom(n as u64).take(n).collect::<Vec<_>>());
+            let len = coll.len();
+            let sorted = coll.into_sorted_vec();
+            assert_eq!(sorted.len(), len);
+            assert!(sorted.windows(2).all(|w| w[0] <= w[1]));
+        }
+
+        let coll = OrderedCollection::from(vec![Box::new(3), Box::new(1), Box::new(2)]);
+        assert_eq!(
+            coll.into_sorted_vec(),
+            vec![Box::new(1), Box::new(2), Box::new(3)]
+        );
+    }
+
+    #[test]
+    fn check_to_sorted_vec() {
+        for n in 0..100 {
+            let mut v = pseudorandom(n as u64).take(n).collect::<Vec<_>>();
+            let coll = OrderedCollection::from(v.clone());
+            v.sort();
+            assert_eq!(coll.to_sorted_vec(), v);
+            assert_eq!(coll.len(), n);
+        }
+    }
+
+    #[test]
+    fn check_has_duplicates() {
+        assert!(OrderedCollection::from(vec![1, 2, 2, 3]).has_duplicates());
+        assert!(OrderedCollection::from(vec![3, 1, 3]).has_duplicates());
+        assert!(OrderedCollection::from(vec![5, 5]).has_duplicates());
+        assert!(!OrderedCollection::from(vec![1, 2, 3]).has_duplicates());
+        assert!(!OrderedCollection::from(vec![1]).has_duplicates());
+        assert!(!OrderedCollection::<u32>::default().has_duplicates());
+
+        let x = OrderedCollection::from((0..1000).collect::<Vec<_>>());
+        assert!(!x.has_duplicates());
+        let x = OrderedCollection::from((0..1000).chain([999]).collect::<Vec<_>>());
+        assert!(x.has_duplicates());
+    }
+
+    #[test]
+    fn check_into_iter_empty() {
+        let values = OrderedCollection::<u32>::from(vec![]);
+        assert_eq!(Vec::from(values), Vec::<u32>::new());
+    }
+
+    #[test]
+    fn check_iter() {
+        let expected = vec![1, 2, 4, 8, 16, 32, 64, 128, 256];
+        let mut values = OrderedCollection::from_sorted_iter(expected.clone())
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+        values.sort();
+        assert_eq!(values, expected);
+    }
+
+    #[test]
+    fn check_iter_sorted() {
+        for n in 0..100 {
+            let mut expected = pseudorandom(n as u64).take(n).collect::<Vec<_>>();
+            let coll = OrderedCollection::from(expected.clone());
+            expected.sort();
+            assert_eq!(coll.iter().copied().collect::<Vec<_>>(), expected);
+            assert_eq!(coll.iter().len(), n);
+            let mut reversed = coll.iter().rev().copied().collect::<Vec<_>>();
+            reversed.reverse();
+            assert_eq!(reversed, expected);
+        }
+    }
+
+    #[test]
+    fn check_iter_empty() {
+        let values = OrderedCollection::<u32>::from(vec![]);
+        assert_eq!(values.iter().next(), None);
+    }
+
+    #[test]
+    fn check_layout_index() {
+        for n in 1..200 {
+            let x = OrderedCollection::from_sorted_iter(0..n);
+            for rank in 0..n {
+                let i = layout_index(n, rank);
+                assert_eq!(x.node(i), Some(&rank), "n = {}", n);
+            }
+        }
+    }
+
+    #[test]
+    fn check_from_eytzinger_vec() {
+        for n in 0..64 {
+            let mut items = vec![0; n];
+            for rank in 0..n {
+                items[layout_index(n, rank) - 1] = 2 * rank as u32;
+            }
+            let x = OrderedCollection::from_eytzinger_vec(items);
+            let y = OrderedCollection::from_sorted_iter((0..n as u32).map(|v| 2 * v));
+            assert_eq!(x.len(), n);
+            assert_eq!(x.first(), y.first());
+            assert_eq!(x.last(), y.last());
+            for v in 0..2 * n as u32 + 1 {
+                assert_eq!(x.find_gte(v), y.find_gte(v));
+                assert_eq!(x.find_lte(v), y.find_lte(v));
+            }
+        }
+    }
+
+    #[test]
+    fn check_as_eytzinger_slice() {
+        for n in 0..64 {
+            let x = OrderedCollection::from(pseudorandom(n as u64).take(n).collect::<Vec<_>>());
+            let items = x.as_eytzinger_slice();
+            assert_eq!(items.len(), n);
+            let y = OrderedCollection::from_eytzinger_vec(items.to_vec());
+            assert_eq!(y.as_eytzinger_slice(), items);
+            for v in 0..1024 {
+                assert_eq!(x.find_gte(v), y.find_gte(v));
+                assert_eq!(x.find_lte(v), y.find_lte(v));
+            }
+        }
+    }
+
+    /// Reference implementation of [`eytzinger_fill()`], walking the tree recursively.
+    fn eytzinger_walk<I, T>(context: &mut (&mut [MaybeUninit<T>], I), i: usize)
+    where
+        I: Iterator<Item = T>,
+    {
+        let (v, _) = context;
+        if i >= v.len() {
+            return;
+        }
+
+        // visit left child
+        eytzinger_walk(context, 2 * i);
+
+        // reborrow context
+        let (v, iter) = context;
+
+        // put data at the root
+        let value = iter.next().unwrap();
+        v[i] = MaybeUninit::new(value);
+
+        // visit right child
+        eytzinger_walk(context, 2 * i + 1);
     }
-}
 
-#[cfg(not(feature = "nightly"))]
-fn do_prefetch<T>(_addr: *const T) {}
+    #[test]
+    fn fill_matches_walk() {
+        let large = pseudorandom(3)
+            .take(8)
+            .map(|n| n as usize * 4096 + n as usize);
+        for n in (0..2000).chain(large) {
+            let mut walked = vec![MaybeUninit::new(0); n + 1];
+            eytzinger_walk(&mut (&mut walked[..], 0..n), 1);
+            let mut filled = vec![MaybeUninit::new(0); n + 1];
+            eytzinger_fill(&mut filled[..], 0..n);
+            for i in 1..=n {
+                // SAFETY: both functions initialize all elements in 1..=n
+                let (walked, filled) =
+                    unsafe { (walked[i].assume_init(), filled[i].assume_init()) };
+                assert_eq!(walked, filled, "n = {}", n);
+            }
+        }
+    }
 
-/// Calculates the prefetch mask for a given collection size.
-///
-/// Creates a binary mask that fully covers a given [`usize`] value (e.g., for the value `0b100`, the mask is `0b111`).
-/// The prefetch mask is used to keep an element address inside the array boundaries when prefetching next values from
-/// memory.
-///
-/// It is totally valid to prefetch invalid addresses from an x86 perspective[^1], but such prefetches do not
-/// aid algorithm performance and may worsen it by thrashing the CPU cache. Instead of prefetching outside
-/// the array boundaries, we use a prefetch mask to zero the offset and prefetch the first elements of the array
-/// instead, aiding subsequent searches. Essentially, masking the offset is a cheaper alternative to the
-/// `offset % size` function.
-///
-/// [^1]: [Intel® 64 and IA-32 Architectures Software Developer’s Manual](https://software.intel.com/en-us/download/intel-64-and-ia-32-architectures-sdm-combined-volumes-1-2a-2b-2c-2d-3a-3b-3c-3d-and-4)
-fn prefetch_mask(n: usize) -> usize {
-    if n > 0 {
-        usize::max_value() >> n.leading_zeros()
-    } else {
-        0
+    #[test]
+    fn check_layout_rank() {
+        for n in 1..300 {
+            for rank in 0..n {
+                assert_eq!(layout_rank(n, layout_index(n, rank)), rank);
+            }
+        }
     }
-}
 
-#[cfg(test)]
-mod tests {
-    use super::*;
-    use alloc::{boxed::Box, vec};
+    #[test]
+    fn check_range() {
+        // covers perfect trees (n = 2^k - 1) as well as unbalanced ones
+        for n in 0..64 {
+            let mut v = pseudorandom(n as u64)
+                .take(n)
+                .map(|v| v % 64)
+                .collect::<Vec<_>>();
+            v.sort_unstable();
+            let x = OrderedCollection::from_sorted_iter(v.iter().copied());
+            for lo in 0..66 {
+                for hi in 0..66 {
+                    let expected = v
+                        .iter()
+                        .filter(|&&v| lo <= v && v <= hi)
+                        .collect::<Vec<_>>();
+                    let range = x.range(lo, hi);
+                    assert_eq!(range.len(), expected.len());
+                    assert_eq!(
+                        range.collect::<Vec<_>>(),
+                        expected,
+                        "{:?} in {}..={}",
+                        v,
+                        lo,
+                        hi
+                    );
+                }
+            }
+        }
+    }
 
     #[test]
-    fn complete_exact() {
-        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
-        assert_eq!(x.find_gte(1), Some(&1));
-        assert_eq!(x.find_gte(2), Some(&2));
-        assert_eq!(x.find_gte(4), Some(&4));
-        assert_eq!(x.find_gte(8), Some(&8));
-        assert_eq!(x.find_gte(16), Some(&16));
-        assert_eq!(x.find_gte(32), Some(&32));
-        assert_eq!(x.find_gte(64), Some(&64));
+    fn check_extend() {
+        let mut expected = pseudorandom(1).take(100).collect::<Vec<_>>();
+        let mut x = OrderedCollection::from(expected.clone());
+        for seed in 2..4 {
+            let batch = pseudorandom(seed).take(77).collect::<Vec<_>>();
+            expected.extend(&batch);
+            x.extend(batch);
+        }
+        expected.sort_unstable();
+
+        assert_eq!(x.len(), expected.len());
+        assert_eq!(x.iter().copied().collect::<Vec<_>>(), expected);
+        for q in 0..1025 {
+            let i = expected.partition_point(|&v| v < q);
+            assert_eq!(x.find_gte(q), expected.get(i));
+        }
     }
 
     #[test]
-    fn complete_approximate() {
-        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64]);
-        assert_eq!(x.find_gte(0), Some(&1));
-        assert_eq!(x.find_gte(3), Some(&4));
-        assert_eq!(x.find_gte(5), Some(&8));
-        assert_eq!(x.find_gte(6), Some(&8));
-        assert_eq!(x.find_gte(7), Some(&8));
-        for i in 9..16 {
-            assert_eq!(x.find_gte(i), Some(&16));
+    fn check_tree_height() {
+        let heights = [
+            (0, 0),
+            (1, 1),
+            (2, 2),
+            (3, 2),
+            (4, 3),
+            (7, 3),
+            (8, 4),
+            (1000, 10),
+            (1023, 10),
+            (1024, 11),
+        ];
+        for (n, height) in heights {
+            let x = OrderedCollection::from_sorted_iter(0..n);
+            assert_eq!(x.tree_height(), height, "n = {}", n);
+
+            let sizes = x.level_sizes();
+            assert_eq!(sizes.len(), height as usize, "n = {}", n);
+            assert_eq!(sizes.iter().sum::<usize>(), n as usize, "n = {}", n);
         }
-        for i in 17..32 {
-            assert_eq!(x.find_gte(i), Some(&32));
+        let x = OrderedCollection::from_sorted_iter(0..12);
+        assert_eq!(x.level_sizes(), vec![1, 2, 4, 5]);
+    }
+
+    #[test]
+    fn check_memory_usage() {
+        let x = OrderedCollection::from((0..1000u64).collect::<Vec<_>>());
+        assert_eq!(
+            x.memory_usage(),
+            mem::size_of::<OrderedCollection<u64>>() + 1001 * 8
+        );
+
+        let x = OrderedCollection::from(vec![(); 10]);
+        assert_eq!(x.memory_usage(), mem::size_of::<OrderedCollection<()>>());
+    }
+
+    #[test]
+    fn check_clear() {
+        use alloc::{
+            format,
+            string::{String, ToString},
+        };
+
+        let mut x = OrderedCollection::from((0..100u32).map(|v| v.to_string()).collect::<Vec<_>>());
+        let memory = x.memory_usage();
+        x.clear();
+        assert_eq!(x.len(), 0);
+        assert!(x.is_empty());
+        assert_eq!(x.capacity(), 100);
+        assert_eq!(x.memory_usage(), memory);
+        assert_eq!(x.find_gte_ref("0"), None);
+        assert_eq!(x.iter().next(), None);
+
+        // the elements are laid out in the same allocation
+        let ptr = x.storage.as_ptr();
+        x.extend_from_sorted_slice(&["a".to_string(), "b".to_string()]);
+        assert_eq!(x.len(), 2);
+        assert_eq!(x.capacity(), 100);
+        assert_eq!(x.storage.as_ptr(), ptr);
+        assert_eq!(x.find_gte_ref("0").map(String::as_str), Some("a"));
+        assert_eq!(x.find_gte_ref("b").map(String::as_str), Some("b"));
+        assert_eq!(x.find_gte_ref("c"), None);
+
+        x.clear();
+        x.clear();
+        assert!(x.is_empty());
+        assert_eq!(x.capacity(), 100);
+
+        // more elements than fit are moved to a new allocation of the exact size
+        x.extend_from_sorted_slice(&(0..150).map(|v| format!("{:03}", v)).collect::<Vec<_>>());
+        assert_eq!(x.len(), 150);
+        assert_eq!(x.capacity(), 150);
+        assert_eq!(x.find_gte_ref("1").map(String::as_str), Some("100"));
+    }
+
+    #[test]
+    fn check_shrink_to_fit() {
+        let mut x = OrderedCollection::from((0..100u32).collect::<Vec<_>>());
+        x.shrink_to_fit();
+        assert_eq!(x.capacity(), 100);
+
+        x.clear();
+        x.extend_from_sorted_slice(&[1, 2, 3]);
+        x.extend(10..20);
+        assert_eq!(x.len(), 13);
+        assert_eq!(x.capacity(), 100);
+        let expected = x.as_eytzinger_slice().to_vec();
+
+        x.shrink_to_fit();
+        assert_eq!(x.capacity(), 13);
+        assert_eq!(x.storage.capacity(), x.len() + 1);
+        assert_eq!(x.as_eytzinger_slice(), expected);
+        assert_eq!(x.storage.as_ptr() as usize % storage::ALIGN, 0);
+        for q in 0..25 {
+            assert_eq!(x.find_gte(q), expected.iter().filter(|&&v| v >= q).min());
         }
-        for i in 33..64 {
-            assert_eq!(x.find_gte(i), Some(&64));
+
+        x.clear();
+        x.shrink_to_fit();
+        assert_eq!(x.capacity(), 0);
+        assert_eq!(x.find_gte(0), None);
+    }
+
+    #[test]
+    fn no_spare_capacity() {
+        let mut x = OrderedCollection::from(vec![1u32, 2, 3]);
+        assert_eq!(x.storage.capacity(), x.len() + 1);
+        x.extend(0..1000);
+        assert_eq!(x.storage.capacity(), x.len() + 1);
+        x.insert(7);
+        assert_eq!(x.storage.capacity(), x.len() + 1);
+        x.extend(0..10);
+        assert_eq!(x.storage.capacity(), x.len() + 1);
+    }
+
+    #[test]
+    fn check_capacity() {
+        for n in 0..100 {
+            let x = OrderedCollection::from_sorted_iter(0..n);
+            assert_eq!(x.capacity(), x.len());
         }
-        assert_eq!(x.find_gte(65), None);
+
+        let mut x = OrderedCollection::from(vec![1, 2, 3]);
+        x.extend(0..10);
+        assert_eq!(x.capacity(), 13);
+        x.retain(|&v| v < 5);
+        assert_eq!(x.capacity(), x.len());
+        assert_eq!(OrderedCollection::<u32>::default().capacity(), 0);
     }
 
     #[test]
-    fn unbalanced_exact() {
-        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64, 128, 256]);
-        assert_eq!(x.find_gte(1), Some(&1));
-        assert_eq!(x.find_gte(2), Some(&2));
-        assert_eq!(x.find_gte(4), Some(&4));
-        assert_eq!(x.find_gte(8), Some(&8));
-        assert_eq!(x.find_gte(16), Some(&16));
-        assert_eq!(x.find_gte(32), Some(&32));
-        assert_eq!(x.find_gte(64), Some(&64));
-        assert_eq!(x.find_gte(128), Some(&128));
-        assert_eq!(x.find_gte(256), Some(&256));
+    fn check_insert() {
+        let mut x = OrderedCollection::from(vec![10, 20, 30]);
+        // middle
+        x.insert(25);
+        assert_eq!(x.find_gte(21), Some(&25));
+        // front
+        x.insert(5);
+        assert_eq!(x.first(), Some(&5));
+        assert_eq!(x.find_gte(0), Some(&5));
+        // past the current max
+        x.insert(40);
+        assert_eq!(x.last(), Some(&40));
+        assert_eq!(x.find_gte(31), Some(&40));
+        // duplicate
+        x.insert(20);
+        assert_eq!(
+            x.iter().copied().collect::<Vec<_>>(),
+            [5, 10, 20, 20, 25, 30, 40]
+        );
     }
 
     #[test]
-    fn unbalanced_approximate() {
-        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64, 128, 256]);
+    fn check_retain() {
+        let mut x = OrderedCollection::from((0..1000).collect::<Vec<_>>());
+        // drop everything between multiples of 10
+        x.retain(|&v| v % 10 == 0);
+        assert_eq!(x.len(), 100);
+        for q in 0..1000 {
+            let expected = (q + 9) / 10 * 10;
+            assert_eq!(x.find_gte(q), Some(&expected).filter(|&&v| v < 1000));
+        }
+
+        x.retain(|&v| v > 500);
+        assert_eq!(x.first(), Some(&510));
+        x.retain(|_| false);
+        assert!(x.is_empty());
+        assert_eq!(x.find_gte(0), None);
+    }
+
+    #[test]
+    fn check_split_off() {
+        let values = vec![1, 3, 3, 5, 7, 7, 9];
+        for (pivot, at) in [(0, 0), (1, 0), (3, 1), (4, 3), (7, 4), (9, 6), (10, 7)] {
+            let mut x = OrderedCollection::from(values.clone());
+            let y = x.split_off(pivot);
+            assert_eq!(x.to_sorted_vec(), values[..at], "pivot = {}", pivot);
+            assert_eq!(y.to_sorted_vec(), values[at..], "pivot = {}", pivot);
+            for q in 0..12 {
+                let expected = values[..at].iter().find(|&&v| v >= q);
+                assert_eq!(x.find_gte(q), expected, "pivot = {}, q = {}", pivot, q);
+                let expected = values[at..].iter().find(|&&v| v >= q);
+                assert_eq!(y.find_gte(q), expected, "pivot = {}, q = {}", pivot, q);
+            }
+        }
+
+        let mut x = OrderedCollection::<u32>::default();
+        assert!(x.split_off(5).is_empty());
+        assert!(x.is_empty());
+    }
+
+    #[test]
+    fn check_dedup() {
+        let mut x = OrderedCollection::from(vec![1, 1, 2, 3, 3, 3]);
+        x.dedup();
+        assert_eq!(x.len(), 3);
+        assert_eq!(x.to_sorted_vec(), vec![1, 2, 3]);
+        assert!(!x.has_duplicates());
         assert_eq!(x.find_gte(0), Some(&1));
-        assert_eq!(x.find_gte(3), Some(&4));
-        assert_eq!(x.find_gte(5), Some(&8));
-        assert_eq!(x.find_gte(6), Some(&8));
-        assert_eq!(x.find_gte(7), Some(&8));
-        for i in 9..16 {
-            assert_eq!(x.find_gte(i), Some(&16));
+        assert_eq!(x.find_gte(2), Some(&2));
+        assert_eq!(x.find_gte(3), Some(&3));
+        assert_eq!(x.find_gte(4), None);
+
+        let mut x = OrderedCollection::<u32>::default();
+        x.dedup();
+        assert!(x.is_empty());
+    }
+
+    #[test]
+    fn check_extend_from_sorted_slice() {
+        // overlapping, with duplicates
+        let mut x = OrderedCollection::from(vec![1, 3, 5, 7]);
+        x.extend_from_sorted_slice(&[2, 3, 6, 10]);
+        assert_eq!(x.to_sorted_vec(), vec![1, 2, 3, 3, 5, 6, 7, 10]);
+        assert_eq!(x.find_gte(4), Some(&5));
+        assert_eq!(x.find_gte(8), Some(&10));
+
+        // disjoint, before and after the existing elements
+        let mut x = OrderedCollection::from(vec![10, 20]);
+        x.extend_from_sorted_slice(&[1, 2]);
+        x.extend_from_sorted_slice(&[30, 40]);
+        assert_eq!(x.to_sorted_vec(), vec![1, 2, 10, 20, 30, 40]);
+        assert_eq!(x.find_gte(25), Some(&30));
+
+        let mut x = OrderedCollection::default();
+        x.extend_from_sorted_slice(&[]);
+        assert!(x.is_empty());
+        x.extend_from_sorted_slice(&[1, 2, 3]);
+        assert_eq!(x, OrderedCollection::from(vec![1, 2, 3]));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "not sorted")]
+    fn from_unsorted_sorted() {
+        let _ = OrderedCollection::from(Sorted(vec![1, 3, 2]));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "not sorted")]
+    fn extend_from_unsorted_slice() {
+        let mut x = OrderedCollection::from(vec![1, 2]);
+        x.extend_from_sorted_slice(&[4, 3]);
+    }
+
+    #[test]
+    fn check_iter_from() {
+        let values = vec![1, 3, 3, 5, 7];
+        let x = OrderedCollection::from(values.clone());
+        for q in 0..9 {
+            let expected = values.iter().filter(|&&v| v >= q).collect::<Vec<_>>();
+            assert_eq!(x.iter_from(q).collect::<Vec<_>>(), expected, "q = {}", q);
+            assert_eq!(x.iter_from(q).len(), expected.len());
         }
-        for i in 17..32 {
-            assert_eq!(x.find_gte(i), Some(&32));
+        // inside, at the boundaries and past the end
+        assert_eq!(x.iter_from(4).copied().collect::<Vec<_>>(), [5, 7]);
+        assert_eq!(x.iter_from(1).copied().collect::<Vec<_>>(), values);
+        assert_eq!(x.iter_from(7).copied().collect::<Vec<_>>(), [7]);
+        assert_eq!(x.iter_from(8).next(), None);
+        assert_eq!(
+            x.iter_from(3).rev().copied().collect::<Vec<_>>(),
+            [7, 5, 3, 3]
+        );
+
+        let x = OrderedCollection::<u32>::default();
+        assert_eq!(x.iter_from(0).next(), None);
+    }
+
+    #[test]
+    fn check_find_gte_n() {
+        let x = OrderedCollection::from(vec![1, 3, 3, 5, 7]);
+        assert_eq!(x.find_gte_n(2, 3), [&3, &3, &5]);
+        assert_eq!(x.find_gte_n(0, 5), [&1, &3, &3, &5, &7]);
+        // more than there are
+        assert_eq!(x.find_gte_n(4, 10), [&5, &7]);
+        assert!(x.find_gte_n(8, 10).is_empty());
+        // none at all
+        assert!(x.find_gte_n(2, 0).is_empty());
+
+        let x = OrderedCollection::<u32>::default();
+        assert!(x.find_gte_n(0, 10).is_empty());
+    }
+
+    #[test]
+    fn check_count_range() {
+        for n in 0..64 {
+            // with few distinct values, most of them are duplicated
+            for modulo in [4, 64] {
+                let v = pseudorandom(n as u64)
+                    .take(n)
+                    .map(|v| v % modulo)
+                    .collect::<Vec<_>>();
+                let x = OrderedCollection::from(v.clone());
+                for lo in 0..modulo + 2 {
+                    for hi in 0..modulo + 2 {
+                        let expected = v.iter().filter(|&&v| lo <= v && v <= hi).count();
+                        assert_eq!(x.count_range(lo, hi), expected, "{}..={}", lo, hi);
+                    }
+                }
+            }
         }
-        for i in 33..64 {
-            assert_eq!(x.find_gte(i), Some(&64));
+    }
+
+    #[test]
+    fn check_partition_point() {
+        let values = [1, 2, 3, 3, 5, 6, 7];
+        let x = OrderedCollection::from(values.to_vec());
+        for q in 0..9 {
+            assert_eq!(
+                x.partition_point(|&v| v < q),
+                values.partition_point(|&v| v < q)
+            );
+            assert_eq!(
+                x.partition_point(|&v| v <= q),
+                values.partition_point(|&v| v <= q)
+            );
         }
-        for i in 65..128 {
-            assert_eq!(x.find_gte(i), Some(&128));
+
+        let x = OrderedCollection::<u32>::default();
+        assert_eq!(x.partition_point(|_| true), 0);
+        assert_eq!(x.partition_point(|_| false), 0);
+    }
+
+    #[test]
+    fn check_merge() {
+        let a = OrderedCollection::from((0..100).step_by(2).collect::<Vec<_>>());
+        let b = OrderedCollection::from((50..150).step_by(5).collect::<Vec<_>>());
+        let mut expected = a.to_sorted_vec();
+        expected.extend(b.to_sorted_vec());
+        expected.sort();
+
+        let x = a.merge(b);
+        assert_eq!(x.len(), 70);
+        assert_eq!(x.to_sorted_vec(), expected);
+        for q in 0..160 {
+            let i = expected.partition_point(|&v| v < q);
+            assert_eq!(x.find_gte(q), expected.get(i));
         }
-        for i in 129..256 {
-            assert_eq!(x.find_gte(i), Some(&256));
+
+        let x = x.merge(OrderedCollection::default());
+        assert_eq!(x.len(), 70);
+        let x = OrderedCollection::default().merge(x);
+        assert_eq!(x.to_sorted_vec(), expected);
+    }
+
+    #[test]
+    fn check_insert_empty() {
+        let mut x = OrderedCollection::from(Vec::new());
+        x.insert(1);
+        assert_eq!(x.len(), 1);
+        assert_eq!(x.find_gte(0), Some(&1));
+    }
+
+    #[test]
+    fn check_eq() {
+        for n in 0..64 {
+            let v = pseudorandom(n as u64).take(n).collect::<Vec<_>>();
+            let mut sorted = v.clone();
+            sorted.sort_unstable();
+            let x = OrderedCollection::from(v);
+            let y = OrderedCollection::from_sorted_iter(sorted.iter().copied());
+            assert_eq!(x, y);
+            assert!(x.iter().eq(y.iter()));
+
+            if n > 0 {
+                sorted[n / 2] += 1024;
+                sorted.sort_unstable();
+                assert_ne!(x, OrderedCollection::from_sorted_iter(sorted));
+            }
         }
-        assert_eq!(x.find_gte(257), None);
+        assert_ne!(
+            OrderedCollection::from(vec![1, 2]),
+            OrderedCollection::from(vec![1, 2, 2])
+        );
     }
 
+    #[cfg(feature = "serde")]
     #[test]
-    fn check_into_iter() {
-        let expected = vec![1, 2, 4, 8, 16, 32, 64, 128, 256];
-        let mut values = OrderedCollection::from_sorted_iter(expected.clone())
-            .into_iter()
-            .collect::<Vec<_>>();
-        values.sort();
-        assert_eq!(values, expected);
+    fn serde_round_trip() {
+        let x = OrderedCollection::from(vec![64u32, 1, 16, 2, 8, 32, 4, 4]);
+        let json = serde_json::to_string(&x).unwrap();
+        assert_eq!(json, "[1,2,4,4,8,16,32,64]");
+
+        let y: OrderedCollection<u32> = serde_json::from_str(&json).unwrap();
+        for i in 0..70 {
+            assert_eq!(x.find_gte(i), y.find_gte(i));
+        }
+
+        assert!(serde_json::from_str::<OrderedCollection<u32>>("[2,1]").is_err());
     }
 
     #[test]
-    fn check_into_iter_empty() {
-        let values = OrderedCollection::<u32>::from(vec![]);
-        assert_eq!(Vec::from(values), vec![]);
+    #[should_panic(expected = "isize::MAX")]
+    fn too_many_elements() {
+        /// Pretends to hold more elements than a collection can address
+        struct Huge;
+
+        impl Iterator for Huge {
+            type Item = u8;
+
+            fn next(&mut self) -> Option<u8> {
+                Some(0)
+            }
+
+            fn size_hint(&self) -> (usize, Option<usize>) {
+                (isize::MAX as usize, Some(isize::MAX as usize))
+            }
+        }
+
+        impl ExactSizeIterator for Huge {}
+
+        OrderedCollection::from_sorted_iter(Huge);
     }
 
     #[test]
-    fn check_iter() {
-        let expected = vec![1, 2, 4, 8, 16, 32, 64, 128, 256];
-        let mut values = OrderedCollection::from_sorted_iter(expected.clone())
-            .iter()
-            .copied()
-            .collect::<Vec<_>>();
-        values.sort();
-        assert_eq!(values, expected);
+    fn large_elements() {
+        /// Occupies a whole cache line, so that deep prefetching kicks in
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Large([u64; 8]);
+
+        assert_eq!(Prefetch::<Large>::MULTIPLIER, 1);
+        let x =
+            OrderedCollection::from_sorted_iter((0..1000usize).map(|v| Large([2 * v as u64; 8])));
+        for v in 0..1000u64 {
+            assert_eq!(x.find_gte(Large([2 * v; 8])), Some(&Large([2 * v; 8])));
+            assert_eq!(
+                x.find_gte(Large([2 * v + 1; 8])).map(|l| l.0[0]),
+                Some(2 * v + 2).filter(|&v| v < 2000)
+            );
+        }
     }
 
     #[test]
-    fn check_iter_empty() {
-        let values = OrderedCollection::<u32>::from(vec![]);
-        assert_eq!(values.iter().next(), None);
+    fn prefetch_offset_wraps() {
+        // the largest index a collection of bytes can reach
+        let i = isize::MAX as usize;
+        let mask = prefetch_mask(i);
+        let offset = Prefetch::<u8>::MULTIPLIER.wrapping_mul(i) & mask;
+        assert!(offset <= mask);
+    }
+
+    #[test]
+    fn zero_sized_type() {
+        let x = OrderedCollection::<()>::from(vec![(), ()]);
+        assert_eq!(x.len(), 2);
+        assert_eq!(x.find_gte(()), Some(&()));
+        assert_eq!(x.find_lte(()), Some(&()));
+        assert_eq!(x.find_gt(()), None);
+        assert_eq!(x.iter().count(), 2);
+
+        let x = OrderedCollection::<()>::from(vec![]);
+        assert_eq!(x.find_gte(()), None);
+    }
+
+    #[test]
+    fn cache_line_aligned() {
+        for n in 0..100 {
+            let x = OrderedCollection::from_sorted_iter(0..n as u32);
+            assert_eq!(x.items().as_ptr() as usize % 64, 0, "n = {}", n);
+            assert_eq!(x.clone().items().as_ptr() as usize % 64, 0, "n = {}", n);
+            assert_eq!(
+                x.iter().copied().collect::<Vec<_>>(),
+                (0..n as u32).collect::<Vec<_>>()
+            );
+        }
+    }
+
+    #[test]
+    fn cache_line_aligned_sizes() {
+        fn check<T: Ord + Copy + Default>() {
+            for n in 0..100 {
+                let mut x = OrderedCollection::from_sorted_iter(vec![T::default(); n]);
+                assert_eq!(x.items().as_ptr() as usize % 64, 0, "n = {}", n);
+                x.extend(vec![T::default(); 3]);
+                assert_eq!(x.items().as_ptr() as usize % 64, 0, "n = {}", n);
+            }
+        }
+        check::<u8>();
+        check::<u16>();
+        check::<u64>();
+        check::<u128>();
+        check::<[u64; 8]>();
     }
 
     #[test]
@@ -640,7 +5085,139 @@ mod tests {
         assert_eq!(prefetch_mask(2), 0b011);
         assert_eq!(prefetch_mask(3), 0b011);
         assert_eq!(prefetch_mask(4), 0b111);
-        assert_eq!(prefetch_mask(usize::max_value()), usize::max_value());
+        assert_eq!(prefetch_mask(usize::MAX), usize::MAX);
+    }
+
+    #[test]
+    fn non_copy_types() {
+        use alloc::string::{String, ToString};
+
+        let x = OrderedCollection::from(vec![String::from("b"), String::from("a")]);
+        assert_eq!(x.find_gte(String::from("ab")), Some(&"b".to_string()));
+        assert_eq!(x.find_gte(String::from("a")), Some(&"a".to_string()));
+        assert_eq!(x.find_gte(String::from("c")), None);
+
+        let x = OrderedCollection::from(vec![vec![3u8], vec![1, 2], vec![1]]);
+        assert_eq!(x.find_gte(vec![1, 1]), Some(&vec![1, 2]));
+        assert_eq!(x.find_gte(vec![2]), Some(&vec![3]));
+        assert_eq!(x.find_lte(vec![2]), Some(&vec![1, 2]));
+    }
+
+    /// Slot `[0]` is never initialized, so elements do not need a placeholder value.
+    #[test]
+    fn no_default_required() {
+        #[derive(Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct NonZero(core::num::NonZeroU32);
+
+        let v = [5, 3, 9, 1]
+            .iter()
+            .map(|&v| NonZero(core::num::NonZeroU32::new(v).unwrap()))
+            .collect::<Vec<_>>();
+        let x = OrderedCollection::from(v);
+        let query = |v| NonZero(core::num::NonZeroU32::new(v).unwrap());
+        assert_eq!(x.find_gte(query(2)), Some(&query(3)));
+        assert_eq!(x.find_gte(query(9)), Some(&query(9)));
+        assert_eq!(x.find_gte(query(10)), None);
+    }
+
+    #[test]
+    fn check_clone() {
+        use alloc::string::{String, ToString};
+
+        let x = OrderedCollection::from(vec![1, 2, 4, 8, 16, 32, 64, 128, 256]);
+        let y = x.clone();
+        for i in 0..260 {
+            assert_eq!(x.find_gte(i), y.find_gte(i));
+        }
+
+        let x = OrderedCollection::from(vec![String::from("b"), String::from("a")]);
+        let y = x.clone();
+        drop(x);
+        assert_eq!(y.find_gte(String::from("ab")), Some(&"b".to_string()));
+    }
+
+    #[test]
+    fn check_debug() {
+        use std::format;
+
+        let x = OrderedCollection::from(vec![8, 1, 4, 2]);
+        assert_eq!(format!("{:?}", x), "OrderedCollection [1, 2, 4, 8]");
+
+        let x = OrderedCollection::<u32>::from(vec![]);
+        assert_eq!(format!("{:?}", x), "OrderedCollection []");
+    }
+
+    #[test]
+    fn send_sync() {
+        fn assert_send_sync<T: Send + Sync>() {}
+        assert_send_sync::<OrderedCollection<u32>>();
+        assert_send_sync::<OrderedCollection<&str>>();
+    }
+
+    #[test]
+    fn shared_between_threads() {
+        use alloc::sync::Arc;
+
+        let x = Arc::new(OrderedCollection::from_sorted_iter(
+            (0..1000).map(|v| 2 * v),
+        ));
+        let threads = (0..4)
+            .map(|t| {
+                let x = Arc::clone(&x);
+                std::thread::spawn(move || {
+                    for v in (t..2000).step_by(4) {
+                        assert_eq!(x.find_gte(v), Some(&(v + v % 2)).filter(|&&v| v < 2000));
+                    }
+                })
+            })
+            .collect::<Vec<_>>();
+        for t in threads {
+            t.join().unwrap();
+        }
+    }
+
+    /// The prefetch addresses are computed past the end of the layout for most sizes, which must not be
+    /// UB (see [`do_prefetch()`]). This test is supposed to be run with `miri`, both with and without
+    /// the `nightly` feature, so it is kept small.
+    #[test]
+    fn prefetch_across_sizes() {
+        fn check<T: Ord + Copy + From<u8> + core::fmt::Debug>() {
+            for n in (0..70).chain([127, 128, 129, 254, 255]) {
+                let x = OrderedCollection::from_sorted_iter((0..n).map(T::from));
+                for q in [0, n / 2, n.saturating_sub(1), n].map(T::from) {
+                    let expected = x.iter().find(|&&v| v >= q);
+                    assert_eq!(x.find_gte(q), expected);
+                    assert_eq!(x.find_gte_with_prefetch_levels::<1, _>(q), expected);
+                    assert_eq!(x.find_gte_with_prefetch_levels::<6, _>(q), expected);
+                    assert_eq!(x.find_gte_linear_tail::<3, _>(q), expected);
+                    let mut out = [None];
+                    x.find_gte_batch(&[q], &mut out);
+                    assert_eq!(out[0], expected);
+                }
+            }
+        }
+
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Large([u64; 8]);
+        impl From<u8> for Large {
+            fn from(v: u8) -> Self {
+                Large([v as u64; 8])
+            }
+        }
+
+        /// Spans two cache lines, so that the multiplier is 0
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+        struct Huge([u64; 16]);
+        impl From<u8> for Huge {
+            fn from(v: u8) -> Self {
+                Huge([v as u64; 16])
+            }
+        }
+
+        check::<u8>();
+        check::<u32>();
+        check::<Large>();
+        check::<Huge>();
     }
 
     /// Because we're using non standard Eytzinger layout with uninitialized first element, we need to ensure that
@@ -0,0 +1,110 @@
+//! Parallel construction with [rayon](https://docs.rs/rayon).
+//!
+//! Sorting is done with `par_sort_unstable`. The elements are then placed into the Eytzinger layout
+//! by filling the slots in parallel: since the rank of the element that goes into slot `i` can be
+//! computed directly (see [`layout_rank()`]), every slot can be filled independently of the others,
+//! and the resulting layout is exactly the same as the sequential one.
+
+use crate::{layout_rank, OrderedCollection};
+use alloc::vec::Vec;
+use core::{mem::MaybeUninit, ptr};
+use rayon::{
+    iter::{
+        FromParallelIterator, IndexedParallelIterator, IntoParallelIterator,
+        IntoParallelRefMutIterator, ParallelIterator,
+    },
+    slice::ParallelSliceMut,
+};
+
+/// Pointer to the sorted elements that can be shared between threads.
+///
+/// The elements are only ever moved out of it, each exactly once, which is fine as long as `T` can
+/// be sent between threads.
+struct Source<T>(*const T);
+
+// SAFETY: see above
+unsafe impl<T: Send> Sync for Source<T> {}
+
+impl<T: Ord + Send> FromParallelIterator<T> for OrderedCollection<T> {
+    /// Construct a new `OrderedCollection` from a parallel iterator over elements in arbitrary order.
+    ///
+    /// The elements are sorted and placed into the layout in parallel. The result is the same as
+    /// with [`FromIterator`](core::iter::FromIterator).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # extern crate rayon;
+    /// # use ordsearch::OrderedCollection;
+    /// use rayon::prelude::*;
+    ///
+    /// let a: OrderedCollection<_> = (0..10_000u32).into_par_iter().rev().collect();
+    /// assert_eq!(a.find_gte(5_000), Some(&5_000));
+    /// ```
+    fn from_par_iter<I: IntoParallelIterator<Item = T>>(iter: I) -> Self {
+        let mut v = Vec::from_par_iter(iter);
+        v.par_sort_unstable();
+        OrderedCollection::from_sorted_vec_par(v)
+    }
+}
+
+impl<T: Send> OrderedCollection<T> {
+    /// Same as [`from_sorted_iter()`](Self::from_sorted_iter), but fills the layout in parallel.
+    fn from_sorted_vec_par(mut v: Vec<T>) -> Self {
+        let n = v.len();
+        let mut storage = Self::allocate(n);
+
+        let source = Source(v.as_ptr());
+        // the elements are moved out below, so `v` must not drop them. Should anything panic, the
+        // elements are leaked rather than dropped twice.
+        // SAFETY: `0 <= capacity`, and there are no elements to initialize
+        unsafe { v.set_len(0) };
+
+        storage[1..]
+            .par_iter_mut()
+            .enumerate()
+            .for_each(|(k, slot)| {
+                let source = &source;
+                // slot `k` holds node `k + 1` of the layout
+                let rank = layout_rank(n, k + 1);
+                // SAFETY: `rank < n`, and every rank is read exactly once since layout_rank() is a
+                // bijection between 1..=n and 0..n
+                *slot = MaybeUninit::new(unsafe { ptr::read(source.0.add(rank)) });
+            });
+
+        Self::from_storage(storage)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::string::ToString;
+    use rayon::slice::ParallelSlice;
+
+    #[test]
+    fn matches_sequential() {
+        for n in (0..300).chain([1 << 16, 100_000]) {
+            let v = (0..n as u64)
+                .map(|v| v.wrapping_mul(0x9e3779b97f4a7c15) % 1000)
+                .collect::<Vec<_>>();
+            let sequential = OrderedCollection::from(v.clone());
+            let parallel = v.into_par_iter().collect::<OrderedCollection<_>>();
+            assert_eq!(
+                sequential.as_eytzinger_slice(),
+                parallel.as_eytzinger_slice()
+            );
+        }
+    }
+
+    #[test]
+    fn moves_elements() {
+        let v = (0..1000).map(|v| v.to_string()).collect::<Vec<_>>();
+        let x = v
+            .par_chunks(10)
+            .flat_map_iter(|c| c.to_vec())
+            .collect::<OrderedCollection<_>>();
+        assert_eq!(x.len(), 1000);
+        assert_eq!(x.find_gte("5".to_string()), Some(&"5".to_string()));
+    }
+}
@@ -0,0 +1,99 @@
+//! Bulk transfer of the layout as bytes with [bytemuck](https://docs.rs/bytemuck).
+//!
+//! For plain-old-data elements, the layout returned by
+//! [`OrderedCollection::as_eytzinger_slice()`] can be viewed as bytes and turned back into a
+//! collection without reordering the elements, e.g. to persist it or to place it in shared memory.
+
+use crate::OrderedCollection;
+use bytemuck::{Pod, PodCastError};
+use core::mem;
+
+impl<T: Pod> OrderedCollection<T> {
+    /// Returns the bytes of the elements in the order they are stored internally.
+    ///
+    /// These are the bytes of [`as_eytzinger_slice()`](Self::as_eytzinger_slice), so there are
+    /// exactly `len() * size_of::<T>()` of them: the uninitialized `[0]` that the collection keeps
+    /// internally is not included. The bytes are in native endianness, and the layout is an
+    /// implementation detail, so only feed them back to [`from_bytes()`](Self::from_bytes) of the
+    /// same version of this crate on the same platform.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1u64, 2, 4, 8]);
+    /// assert_eq!(x.as_bytes().len(), 4 * 8);
+    /// ```
+    pub fn as_bytes(&self) -> &[u8] {
+        bytemuck::cast_slice(self.as_eytzinger_slice())
+    }
+
+    /// Construct a new `OrderedCollection` from bytes returned by [`as_bytes()`](Self::as_bytes).
+    ///
+    /// `bytes` doesn't need to be aligned for `T`, as the elements are copied into a new allocation
+    /// (which [`OrderedCollection`] needs anyway to align the layout). Returns an error if the
+    /// length of `bytes` is not a multiple of the size of `T`, or if `T` is zero-sized, in which case
+    /// the number of elements can't be recovered from the bytes.
+    ///
+    /// As with [`from_eytzinger_vec()`](Self::from_eytzinger_vec), bytes that don't hold a valid
+    /// layout don't cause an error, but lookups will give incorrect results.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use ordsearch::OrderedCollection;
+    /// let x = OrderedCollection::from(vec![1u64, 2, 4, 8]);
+    /// let y = OrderedCollection::<u64>::from_bytes(x.as_bytes()).unwrap();
+    /// assert_eq!(y.find_gte(3), Some(&4));
+    ///
+    /// assert!(OrderedCollection::<u64>::from_bytes(&[0; 7]).is_err());
+    /// ```
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, PodCastError> {
+        let size = mem::size_of::<T>();
+        if size == 0 || bytes.len() % size != 0 {
+            return Err(PodCastError::OutputSliceWouldHaveSlop);
+        }
+        let items = bytemuck::pod_collect_to_vec(bytes);
+        Ok(Self::from_eytzinger_vec(items))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloc::{vec, vec::Vec};
+
+    #[test]
+    fn round_trip() {
+        for n in 0..100usize {
+            let x = OrderedCollection::from_sorted_iter((0..n).map(|v| 3 * v as u64));
+            let bytes = x.as_bytes();
+            assert_eq!(bytes.len(), n * 8);
+
+            let y = OrderedCollection::<u64>::from_bytes(bytes).unwrap();
+            assert_eq!(x, y);
+            for q in 0..3 * n as u64 + 2 {
+                assert_eq!(y.find_gte(q), x.find_gte(q));
+            }
+        }
+    }
+
+    #[test]
+    fn unaligned() {
+        let x = OrderedCollection::from(vec![5u64, 1, 3]);
+        let mut buf = Vec::with_capacity(x.as_bytes().len() + 1);
+        buf.push(0);
+        buf.extend_from_slice(x.as_bytes());
+        let y = OrderedCollection::<u64>::from_bytes(&buf[1..]).unwrap();
+        assert_eq!(x, y);
+    }
+
+    #[test]
+    fn rejects_slop() {
+        assert!(OrderedCollection::<u32>::from_bytes(&[0; 5]).is_err());
+        assert!(OrderedCollection::<()>::from_bytes(&[]).is_err());
+        assert!(OrderedCollection::<u32>::from_bytes(&[])
+            .unwrap()
+            .is_empty());
+    }
+}
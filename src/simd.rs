@@ -0,0 +1,74 @@
+//! Vectorized search step for small integer element types.
+//!
+//! When the three levels of the tree below node `i` are complete, they hold 7 elements: `i`, its two
+//! children at `2i` and `2i + 1` and its four grandchildren at `4i..4i + 4`. Because the elements are
+//! sorted in-order, the number of those elements that are smaller than the query is exactly the
+//! position (`0..8`) of the query among the 8 great-grandchildren of `i`. So, the branch-free descent
+//! over these three levels ends up at `8i + count`. All 7 loads only depend on `i`, so they can be
+//! issued at once instead of one after another, and the grandchildren are contiguous in memory, so
+//! they can be compared with a single vector instruction.
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for u16 {}
+    impl Sealed for u32 {}
+}
+
+/// Element types that support the vectorized [`find_gte_simd()`](crate::OrderedCollection::find_gte_simd).
+///
+/// This trait is sealed and implemented for `u16` and `u32`.
+pub trait SimdElement: Ord + Copy + sealed::Sealed {
+    /// Returns the number of elements in `nodes` that are smaller than `x`.
+    #[doc(hidden)]
+    fn count_lt(x: Self, nodes: &[Self; 4]) -> usize;
+}
+
+impl SimdElement for u32 {
+    #[cfg(target_arch = "x86_64")]
+    #[inline(always)]
+    fn count_lt(x: Self, nodes: &[Self; 4]) -> usize {
+        use core::arch::x86_64::*;
+
+        // SAFETY: SSE2 is always available on x86_64, and `nodes` is 16 bytes long
+        unsafe {
+            // SSE2 only has signed comparison, flipping the sign bit turns it into unsigned one
+            let bias = _mm_set1_epi32(i32::MIN);
+            let v = _mm_xor_si128(_mm_loadu_si128(nodes.as_ptr().cast()), bias);
+            let q = _mm_xor_si128(_mm_set1_epi32(x as i32), bias);
+            let gt = _mm_cmpgt_epi32(q, v);
+            _mm_movemask_ps(_mm_castsi128_ps(gt)).count_ones() as usize
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    #[inline(always)]
+    fn count_lt(x: Self, nodes: &[Self; 4]) -> usize {
+        nodes.iter().map(|&v| usize::from(x > v)).sum()
+    }
+}
+
+impl SimdElement for u16 {
+    #[cfg(target_arch = "x86_64")]
+    #[inline(always)]
+    fn count_lt(x: Self, nodes: &[Self; 4]) -> usize {
+        use core::arch::x86_64::*;
+
+        // SAFETY: SSE2 is always available on x86_64, and `nodes` is 8 bytes long
+        unsafe {
+            // SSE2 only has signed comparison, flipping the sign bit turns it into unsigned one
+            let bias = _mm_set1_epi16(i16::MIN);
+            let v = _mm_xor_si128(_mm_loadl_epi64(nodes.as_ptr().cast()), bias);
+            let q = _mm_xor_si128(_mm_set1_epi16(x as i16), bias);
+            let gt = _mm_cmpgt_epi16(q, v);
+            // each 16-bit lane sets two bits of the byte mask, and only the lower 4 lanes are loaded
+            (_mm_movemask_epi8(gt) & 0xff).count_ones() as usize / 2
+        }
+    }
+
+    #[cfg(not(target_arch = "x86_64"))]
+    #[inline(always)]
+    fn count_lt(x: Self, nodes: &[Self; 4]) -> usize {
+        nodes.iter().map(|&v| usize::from(x > v)).sum()
+    }
+}
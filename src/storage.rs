@@ -0,0 +1,151 @@
+//! Cache line aligned allocation for the layout.
+//!
+//! A `Vec<T>` is only aligned for `T`, so `[0]` of the layout would start at an arbitrary place in a
+//! cache line. [`Storage`] manages the allocation itself to always place `[0]` at a cache line
+//! boundary, whatever the size of `T`.
+
+use alloc::alloc::{alloc, dealloc, handle_alloc_error, Layout};
+use core::{
+    mem::{self, MaybeUninit},
+    ops::{Deref, DerefMut},
+    ptr::NonNull,
+    slice,
+};
+
+/// Alignment of the allocation in bytes, i.e. the size of a cache line.
+pub(crate) const ALIGN: usize = 64;
+
+/// A buffer of possibly uninitialized elements, aligned to [`ALIGN`].
+///
+/// Like `Vec<MaybeUninit<T>>`, it never drops the elements, only frees the allocation. The buffer
+/// can't grow, but its length can be changed within the capacity it was allocated with.
+pub(crate) struct Storage<T> {
+    ptr: NonNull<MaybeUninit<T>>,
+    len: usize,
+    cap: usize,
+}
+
+// SAFETY: `Storage` owns its elements, like `Vec` does
+unsafe impl<T: Send> Send for Storage<T> {}
+// SAFETY: `Storage` only gives out shared references to its elements through `&self`
+unsafe impl<T: Sync> Sync for Storage<T> {}
+
+impl<T> Storage<T> {
+    /// Allocates a buffer of `len` uninitialized elements.
+    ///
+    /// Panics if the buffer would be larger than `isize::MAX` bytes.
+    pub(crate) fn new(len: usize) -> Self {
+        let layout = Self::layout(len);
+        let ptr = if layout.size() == 0 {
+            // no allocation is needed, but the pointer still must be aligned, and to a cache line
+            // rather than just to `T` like `NonNull::dangling()` is
+            layout.align() as *mut MaybeUninit<T>
+        } else {
+            // SAFETY: the size of the layout is not zero
+            let ptr = unsafe { alloc(layout) };
+            if ptr.is_null() {
+                handle_alloc_error(layout);
+            }
+            ptr.cast()
+        };
+        Storage {
+            // SAFETY: `ptr` is not null, as the alignment is not zero and a failed allocation diverges
+            ptr: unsafe { NonNull::new_unchecked(ptr) },
+            len,
+            cap: len,
+        }
+    }
+
+    /// Returns the number of elements the buffer was allocated for.
+    pub(crate) fn capacity(&self) -> usize {
+        self.cap
+    }
+
+    /// Sets the length of the buffer, which must not exceed its capacity.
+    ///
+    /// The elements are possibly uninitialized anyway, so this doesn't initialize or drop any of them.
+    pub(crate) fn set_len(&mut self, len: usize) {
+        assert!(len <= self.cap, "Storage length exceeds its capacity");
+        self.len = len;
+    }
+
+    /// Returns the layout of the allocation for `len` elements.
+    fn layout(len: usize) -> Layout {
+        let size = mem::size_of::<T>()
+            .checked_mul(len)
+            .expect("OrderedCollection storage is too large");
+        Layout::from_size_align(size, mem::align_of::<T>().max(ALIGN))
+            .expect("OrderedCollection storage is too large")
+    }
+}
+
+impl<T> Default for Storage<T> {
+    fn default() -> Self {
+        Storage::new(0)
+    }
+}
+
+impl<T> Deref for Storage<T> {
+    type Target = [MaybeUninit<T>];
+
+    fn deref(&self) -> &Self::Target {
+        // SAFETY: `ptr` is valid for `cap >= len` elements, which don't need to be initialized
+        unsafe { slice::from_raw_parts(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> DerefMut for Storage<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        // SAFETY: `ptr` is valid for `cap >= len` elements, which don't need to be initialized
+        unsafe { slice::from_raw_parts_mut(self.ptr.as_ptr(), self.len) }
+    }
+}
+
+impl<T> Drop for Storage<T> {
+    fn drop(&mut self) {
+        let layout = Self::layout(self.cap);
+        if layout.size() != 0 {
+            // SAFETY: the buffer was allocated in `new()` with the same layout
+            unsafe { dealloc(self.ptr.as_ptr().cast(), layout) };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn aligned() {
+        fn check<T>() {
+            for len in 0..100 {
+                let s = Storage::<T>::new(len);
+                assert_eq!(s.len(), len);
+                assert_eq!(s.as_ptr() as usize % ALIGN, 0, "len = {}", len);
+            }
+        }
+        check::<u8>();
+        check::<u32>();
+        check::<[u8; 3]>();
+        check::<[u64; 8]>();
+        check::<[u8; 100]>();
+        check::<()>();
+    }
+
+    #[test]
+    fn set_len() {
+        let mut s = Storage::<u32>::new(10);
+        s.set_len(3);
+        assert_eq!(s.len(), 3);
+        assert_eq!(s.capacity(), 10);
+        s.set_len(10);
+        assert_eq!(s.len(), 10);
+        assert!(std::panic::catch_unwind(move || s.set_len(11)).is_err());
+    }
+
+    #[test]
+    fn too_large() {
+        let result = std::panic::catch_unwind(|| Storage::<u64>::new(usize::MAX / 4));
+        assert!(result.is_err());
+    }
+}
@@ -0,0 +1,49 @@
+//! Checks that `OrderedArray` never allocates.
+//!
+//! This replaces the global allocator, so it lives in its own test binary rather than among the
+//! unit tests, which would all run through it. CI also runs it without the `alloc` feature, where
+//! the crate doesn't link `alloc` at all.
+
+extern crate ordsearch;
+
+use ordsearch::OrderedArray;
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    cell::Cell,
+};
+
+/// Counts the allocations of every thread, so that tests can check they don't allocate
+struct CountingAllocator;
+
+std::thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+// SAFETY: all the work is delegated to the system allocator
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        // the thread local is gone while the thread shuts down, these allocations don't matter
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+#[test]
+fn no_alloc() {
+    let before = ALLOCATIONS.with(Cell::get);
+    let x = OrderedArray::from_sorted_array([(1, 'a'), (4, 'b'), (9, 'c')]);
+    assert!(!x.is_empty());
+    let gte = x.find_gte((2, 'a'));
+    let lte = x.find_lte((2, 'a'));
+    assert_eq!(ALLOCATIONS.with(Cell::get), before);
+
+    assert_eq!(gte, Some(&(4, 'b')));
+    assert_eq!(lte, Some(&(1, 'a')));
+}